@@ -0,0 +1,159 @@
+
+use std::collections::HashMap;
+
+use Graph;
+use path::Path;
+use dir::DirT;
+use edge::{EdgeT, WeightT, NumT};
+use vertex::{NodeT, Vertex};
+use super::shortest_path::{self, NegativeCycle};
+
+///////////////////////////////////////////////////////////////////////////////
+//  All-pairs results
+///////////////////////////////////////////////////////////////////////////////
+
+/// Shortest distance between every pair of vertices, plus the first step
+///  to take from one towards the other
+#[derive(Debug)]
+pub struct AllPairs<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>, W> {
+    verts: Vec<&'a Vertex<V,E,D>>,
+    index: HashMap<&'a V, usize>,
+    // row-major `n`×`n` matrices, indexed by `index`
+    dist: Vec<Option<W>>,
+    next: Vec<Option<(usize, &'a E)>>,
+}
+
+impl<'a, V: NodeT, E: EdgeT, D: DirT<V,E>, W: NumT> AllPairs<'a,V,E,D,W> {
+    fn new(g: &'a Graph<V,E,D>) -> Self {
+        let verts: Vec<_> = g.vertices().collect();
+        let index = verts.iter().enumerate().map(|(i, v)| (v.get(), i)).collect();
+        let n = verts.len();
+        let mut dist = vec![None; n * n];
+        for i in 0..n {
+            dist[i * n + i] = Some(W::zero());
+        }
+        AllPairs { verts, index, dist, next: vec![None; n * n] }
+    }
+    fn at(&self, i: usize, j: usize) -> usize {
+        i * self.verts.len() + j
+    }
+
+    /// Length of the shortest path from `l` to `r`, or `None` if unreachable
+    pub fn distance(&self, l: &V, r: &V) -> Option<W> {
+        let (&i, &j) = (self.index.get(l)?, self.index.get(r)?);
+        self.dist[self.at(i, j)]
+    }
+    /// The vertex after `l` on the shortest path from `l` to `r`
+    pub fn next_hop(&self, l: &V, r: &V) -> Option<&'a Vertex<V,E,D>> {
+        let (&i, &j) = (self.index.get(l)?, self.index.get(r)?);
+        self.next[self.at(i, j)].map(|(k, _)| self.verts[k])
+    }
+    /// Follow next hops from `l` until reaching `r`
+    pub fn path(&self, l: &V, r: &V) -> Option<Path<'a,V,E,D>> {
+        let (&i, &j) = (self.index.get(l)?, self.index.get(r)?);
+        self.dist[self.at(i, j)]?;
+        let mut path = Path::new(self.verts[i]);
+        let mut cur = i;
+        while cur != j {
+            let (k, edge) = self.next[self.at(cur, j)]?;
+            path.push(edge, self.verts[k]);
+            cur = k;
+        }
+        Some(path)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Floyd-Warshall
+///////////////////////////////////////////////////////////////////////////////
+
+/// O(n³) time and O(n²) space regardless of the number of edges
+pub(crate) fn floyd_warshall<'a, V, E, D>(g: &'a Graph<V,E,D>)
+    -> Result<AllPairs<'a, V, E, D, E::W>, NegativeCycle<'a,V,E,D>>
+    where V: NodeT, E: WeightT, D: DirT<V,E>
+{
+    let mut ap = AllPairs::new(g);
+    let n = ap.verts.len();
+    for i in 0..n {
+        for (next, edge) in g.get_reachable(ap.verts[i]).with_edges() {
+            let j = ap.index[next.get()];
+            let ij = ap.at(i, j);
+            let w = edge.weight();
+            if ap.dist[ij].is_none_or(|old| w < old) {
+                ap.dist[ij] = Some(w);
+                ap.next[ij] = Some((j, edge));
+            }
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            let ik = match ap.dist[ap.at(i, k)] {
+                Some(ik) => ik,
+                None => continue,
+            };
+            for j in 0..n {
+                if let Some(kj) = ap.dist[ap.at(k, j)] {
+                    let (ij, alt) = (ap.at(i, j), ik + kj);
+                    if ap.dist[ij].is_none_or(|old| alt < old) {
+                        ap.dist[ij] = Some(alt);
+                        ap.next[ij] = ap.next[ap.at(i, k)];
+                    }
+                }
+            }
+        }
+    }
+    for i in 0..n {
+        if ap.dist[ap.at(i, i)].is_some_and(|d| d < E::W::zero()) {
+            // `i` is on a negative cycle, so Bellman-Ford from it will find one
+            let cycle = shortest_path::bellman_ford(g, ap.verts[i]).err();
+            return Err(cycle.expect("Floyd-Warshall found a cycle Bellman-Ford didn't"));
+        }
+    }
+    Ok(ap)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Johnson
+///////////////////////////////////////////////////////////////////////////////
+
+/// Reweights edges to be non-negative then runs Dijkstra from every vertex;
+///  O(nm log n), so better than Floyd-Warshall for sparse graphs
+pub(crate) fn johnson<'a, V, E, D>(g: &'a Graph<V,E,D>)
+    -> Result<AllPairs<'a, V, E, D, E::W>, NegativeCycle<'a,V,E,D>>
+    where V: NodeT, E: WeightT, D: DirT<V,E>
+{
+    let h = shortest_path::potentials(g)?;
+    let mut ap = AllPairs::new(g);
+    let n = ap.verts.len();
+    for i in 0..n {
+        let src = ap.verts[i];
+        let sp = shortest_path::dijkstra_by(g, src, |l, e, r| {
+            e.weight() + h[l.get()] - h[r.get()]
+        });
+        // the first step towards `v` is the first step towards its predecessor
+        let mut first: HashMap<&V, (usize, &E)> = HashMap::with_capacity(n);
+        for (&v, &d) in sp.distances() {
+            let j = ap.index[v];
+            let ij = ap.at(i, j);
+            ap.dist[ij] = Some(d + h[v] - h[src.get()]);
+            let mut chain = vec![];
+            let mut cur = v;
+            let hop = loop {
+                if let Some(&hop) = first.get(cur) { break Some(hop) }
+                match sp.predecessor(cur) {
+                    Some((prev, edge)) if prev.get() == src.get() => {
+                        break Some((ap.index[cur], edge))
+                    }
+                    Some((prev, _)) => { chain.push(cur); cur = prev.get(); }
+                    None => break None,
+                }
+            };
+            if let Some(hop) = hop {
+                first.insert(cur, hop);
+                for c in chain { first.insert(c, hop); }
+            }
+            ap.next[ij] = hop;
+        }
+    }
+    Ok(ap)
+}
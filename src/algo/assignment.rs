@@ -0,0 +1,163 @@
+
+use std::collections::{HashMap, HashSet};
+
+use UndirectedGraph;
+use dir::Undir;
+use edge::{EdgeT, WeightT, NumT};
+use path::Cycle;
+use vertex::{NodeT, Vertex};
+use super::Matching;
+use super::matching::hopcroft_karp_by;
+
+type UVertex<V,E> = Vertex<V, E, Undir<V,E>>;
+
+///////////////////////////////////////////////////////////////////////////////
+//  Assignment results
+///////////////////////////////////////////////////////////////////////////////
+
+/// A perfect matching between the two sides of a bipartite graph alongside
+///  its total weight
+#[derive(Debug)]
+pub struct Assignment<'a, V: 'a+NodeT, E: 'a+EdgeT, W> {
+    matching: Matching<'a,V,E>,
+    cost: W,
+}
+
+impl<'a, V: NodeT, E: EdgeT, W: NumT> Assignment<'a,V,E,W> {
+    pub fn matching(&self) -> &Matching<'a,V,E> {
+        &self.matching
+    }
+    pub fn into_matching(self) -> Matching<'a,V,E> {
+        self.matching
+    }
+    /// Sum of the weights of every assigned edge
+    pub fn cost(&self) -> W {
+        self.cost
+    }
+}
+
+#[derive(Debug)]
+pub enum AssignmentError<'a, V: 'a+NodeT, E: 'a+EdgeT> {
+    /// The graph has no two sides, as shown by this odd cycle
+    NotBipartite(Cycle<'a, V, E, Undir<V,E>>),
+    /// Hall's condition fails: `vertices` are all on one side and are only
+    ///  adjacent to `neighbors`, of which there are fewer
+    Deficient {
+        vertices: Vec<&'a UVertex<V,E>>,
+        neighbors: Vec<&'a UVertex<V,E>>,
+    },
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Hungarian algorithm
+///////////////////////////////////////////////////////////////////////////////
+
+/// As `hungarian_by`, with the sides found by two-coloring the graph
+pub(crate) fn hungarian<V, E>(g: &UndirectedGraph<V,E>, maximize: bool)
+    -> Result<Assignment<'_, V, E, E::W>, AssignmentError<'_,V,E>>
+    where V: NodeT, E: WeightT
+{
+    let (left, _) = g.bipartition().map_err(AssignmentError::NotBipartite)?;
+    let left: HashSet<&V> = left.into_iter().map(|v| v.get()).collect();
+    hungarian_by(g, |v| left.contains(v), maximize)
+}
+
+/// Perfect matching of least (or greatest) total weight between the vertices
+///  for which `is_left` holds and the rest; edges within one side are ignored.
+///  O(n³) in the size of a side
+pub(crate) fn hungarian_by<V, E, F>(g: &UndirectedGraph<V,E>, is_left: F, maximize: bool)
+    -> Result<Assignment<'_, V, E, E::W>, AssignmentError<'_,V,E>>
+    where V: NodeT, E: WeightT, F: Fn(&V) -> bool
+{
+    let (left, right): (Vec<_>, Vec<_>) = g.vertices().partition(|v| is_left(v.get()));
+    // Hungarian needs some perfect matching to exist, so check with Hopcroft-Karp
+    //  from the larger side, whose unmatched vertices witness any deficiency
+    let big_is_left = left.len() >= right.len();
+    let (matching, cover) = hopcroft_karp_by(g, |v| is_left(v) == big_is_left);
+    if matching.len() < left.len().max(right.len()) {
+        let in_big = |v: &UVertex<V,E>| is_left(v.get()) == big_is_left;
+        let covered: HashSet<&V> = cover.iter().map(|v| v.get()).collect();
+        let vertices = g.vertices().filter(|v| in_big(v) && covered.contains(v.get()) == false).collect();
+        let neighbors = cover.into_iter().filter(|v| in_big(v) == false).collect();
+        return Err(AssignmentError::Deficient { vertices, neighbors });
+    }
+
+    let n = left.len();
+    let r_index: HashMap<&V, usize> = right.iter().enumerate().map(|(j, v)| (v.get(), j)).collect();
+    // maximizing `w` is minimizing `max - w`, which stays non-negative
+    let max = g.edges().map(|e| e.weight()).max().unwrap_or_else(E::W::zero);
+    let price = |e: &E| if maximize { max - e.weight() } else { e.weight() };
+    // the best of any parallel edges; `None` if there's no edge at all
+    let mut cost: Vec<Option<(E::W, &E)>> = vec![None; n * n];
+    for (i, &l) in left.iter().enumerate() {
+        for (r, e) in g.get_neighbors(l).with_edges() {
+            if let Some(&j) = r_index.get(r.get()) {
+                if cost[i * n + j].is_none_or(|(c, _)| price(e) < c) {
+                    cost[i * n + j] = Some((price(e), e));
+                }
+            }
+        }
+    }
+
+    // rows are added one at a time, each along a shortest augmenting path;
+    //  column 0 is a sentinel holding the row being added. Reduced costs are
+    //  `cost + v - u`, so `v` is negated from the usual presentation to keep
+    //  every potential non-negative
+    let zero = E::W::zero();
+    let (mut u, mut v) = (vec![zero; n + 1], vec![zero; n + 1]);
+    let mut row_of = vec![0; n + 1];
+    let mut way = vec![0; n + 1];
+    for i in 1 ..= n {
+        row_of[0] = i;
+        let mut j0 = 0;
+        let mut min_to: Vec<Option<E::W>> = vec![None; n + 1];
+        let mut used = vec![false; n + 1];
+        loop {
+            used[j0] = true;
+            let i0 = row_of[j0];
+            let mut next: Option<(E::W, usize)> = None;
+            for j in 1 ..= n {
+                if used[j] { continue }
+                if let Some((c, _)) = cost[(i0 - 1) * n + (j - 1)] {
+                    let reduced = c + v[j] - u[i0];
+                    if min_to[j].is_none_or(|m| reduced < m) {
+                        min_to[j] = Some(reduced);
+                        way[j] = j0;
+                    }
+                }
+                if let Some(m) = min_to[j] {
+                    if next.is_none_or(|(d, _)| m < d) {
+                        next = Some((m, j));
+                    }
+                }
+            }
+            let (delta, j1) = next.expect("Hall's condition held but the tree is stuck");
+            for j in 0 ..= n {
+                if used[j] {
+                    u[row_of[j]] = u[row_of[j]] + delta;
+                    v[j] = v[j] + delta;
+                } else if let Some(m) = min_to[j] {
+                    min_to[j] = Some(m - delta);
+                }
+            }
+            j0 = j1;
+            if row_of[j0] == 0 { break }
+        }
+        // flip the alternating path back to the sentinel
+        while j0 != 0 {
+            let j1 = way[j0];
+            row_of[j0] = row_of[j1];
+            j0 = j1;
+        }
+    }
+
+    let mut total = zero;
+    let mut pairs = Vec::with_capacity(n);
+    for j in 1 ..= n {
+        let i = row_of[j];
+        let (_, e) = cost[(i - 1) * n + (j - 1)].expect("assigned along a missing edge");
+        total = total + e.weight();
+        pairs.push((left[i - 1], e, right[j - 1]));
+    }
+    Ok(Assignment { matching: Matching::new(pairs), cost: total })
+}
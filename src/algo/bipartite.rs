@@ -0,0 +1,72 @@
+
+use std::collections::HashMap;
+
+use Graph;
+use dir::DirT;
+use edge::EdgeT;
+use iter::BreadthFirst;
+use path::{Path, Cycle};
+use vertex::{NodeT, Vertex};
+
+///////////////////////////////////////////////////////////////////////////////
+//  Two-coloring
+///////////////////////////////////////////////////////////////////////////////
+
+/// Color vertices by the parity of their breadth-first depth; an edge within
+///  one layer closes an odd cycle through the layers above it. Edge direction
+///  is ignored, since it doesn't change which pairs of vertices are joined
+pub(crate) fn bipartition<V, E, D>(g: &Graph<V,E,D>)
+    -> Result<(Vec<&Vertex<V,E,D>>, Vec<&Vertex<V,E,D>>), Cycle<'_,V,E,D>>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>
+{
+    let mut depth: HashMap<&V, usize> = HashMap::with_capacity(g.order());
+    for root in g.vertices() {
+        if depth.contains_key(root.get()) { continue }
+        let mut bfs = BreadthFirst::both_ways(g, Some(root));
+        while let Some(v) = bfs.next() {
+            depth.insert(v.get(), bfs.depth());
+        }
+    }
+    for u in g.vertices() {
+        for (w, e) in g.get_reachable(u).with_edges() {
+            if depth[u.get()] == depth[w.get()] {
+                return Err(odd_cycle(g, &depth, u, e, w));
+            }
+        }
+    }
+    Ok(g.vertices().partition(|v| depth[v.get()].is_multiple_of(2)))
+}
+
+fn odd_cycle<'a, V, E, D>(g: &'a Graph<V,E,D>,
+                          depth: &HashMap<&V, usize>,
+                          u: &'a Vertex<V,E,D>,
+                          e: &'a E,
+                          w: &'a Vertex<V,E,D>)
+    -> Cycle<'a,V,E,D>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>
+{
+    // any neighbor one layer up is a breadth-first parent
+    let parent = |v: &'a Vertex<V,E,D>| g.get_adjacent(v).with_edges()
+        .find(|&(p, _)| depth[p.get()] + 1 == depth[v.get()])
+        .expect("vertex below the root without a parent");
+    // climb from both ends in lockstep until they meet
+    let (mut a, mut b) = (u, w);
+    let (mut up_a, mut up_b) = (vec![], vec![]);
+    while a.get() != b.get() {
+        let (pa, ea) = parent(a);
+        let (pb, eb) = parent(b);
+        up_a.push((ea, a));
+        up_b.push((eb, pb));
+        a = pa;
+        b = pb;
+    }
+    let mut path = Path::new(a);
+    for (edge, down) in up_a.into_iter().rev() {
+        path.push(edge, down);
+    }
+    path.push(e, w);
+    for (edge, up) in up_b {
+        path.push(edge, up);
+    }
+    Cycle::new(path)
+}
@@ -0,0 +1,205 @@
+
+use std::collections::HashMap;
+
+use Graph;
+use dir::DirT;
+use edge::{EdgeT, NumT};
+use vertex::{NodeT, Vertex};
+use super::{EdgeRef, edge_list};
+use super::shortest_path::Settled;
+
+/// Power iterations stop once scores change by less than this in total
+const TOLERANCE: f64 = 1e-10;
+const MAX_ITERATIONS: usize = 1000;
+
+/// Vertices numbered with the edges out of each as `(index, weight)`
+fn adjacency<V, E, D, F>(g: &Graph<V,E,D>, weight: F) -> (Vec<&Vertex<V,E,D>>, Vec<Vec<(usize, f64)>>)
+    where V: NodeT, E: EdgeT, D: DirT<V,E>, F: Fn(&E) -> f64
+{
+    let verts: Vec<_> = g.vertices().collect();
+    let index: HashMap<&V, usize> = verts.iter().enumerate().map(|(i, v)| (v.get(), i)).collect();
+    let out = verts.iter().map(|&v| {
+        g.get_reachable(v).with_edges().map(|(r, e)| (index[r.get()], weight(e))).collect()
+    }).collect();
+    (verts, out)
+}
+
+fn by_vertex<'a, V, E, D>(verts: &[&'a Vertex<V,E,D>], scores: Vec<f64>) -> HashMap<&'a V, f64>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>
+{
+    verts.iter().zip(scores).map(|(v, s)| (v.get(), s)).collect()
+}
+
+/// Scale `x` so its entries' absolute values (or their squares) sum to one
+fn normalize(x: &mut [f64], squared: bool) {
+    let norm = if squared {
+        x.iter().map(|a| a * a).sum::<f64>().sqrt()
+    } else {
+        x.iter().map(|a| a.abs()).sum()
+    };
+    if norm > 0.0 {
+        x.iter_mut().for_each(|a| *a /= norm);
+    }
+}
+
+fn change(x: &[f64], y: &[f64]) -> f64 {
+    x.iter().zip(y).map(|(a, b)| (a - b).abs()).sum()
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Betweenness
+///////////////////////////////////////////////////////////////////////////////
+
+/// How many shortest paths between other pairs go through each vertex and
+///  along each edge (each counted fractionally when there are ties), by
+///  Brandes' algorithm in O(nm + n² log n). `settle` finds every shortest
+///  path from one vertex, so weights must be positive
+pub(crate) fn brandes<'a, V, E, D, W, S>(g: &'a Graph<V,E,D>, settle: S)
+    -> (HashMap<&'a V, f64>, Vec<(EdgeRef<'a,V,E,D>, f64)>)
+    where V: NodeT, E: EdgeT, D: DirT<V,E>, W: NumT, S: Fn(&'a Vertex<V,E,D>) -> Settled<'a,V,E,D,W>
+{
+    let mut through: HashMap<&V, f64> = g.vertices().map(|v| (v.get(), 0.0)).collect();
+    let mut along: HashMap<*const E, f64> = HashMap::with_capacity(g.size());
+    for s in g.vertices() {
+        let paths = settle(s);
+        // the share of paths from `s` to everything past each vertex
+        let mut past: HashMap<&V, f64> = HashMap::with_capacity(paths.order.len());
+        for &w in paths.order.iter().rev() {
+            let beyond = past.get(w.get()).cloned().unwrap_or(0.0);
+            for &(v, e) in &paths.preds[w.get()] {
+                let share = paths.count[v.get()] / paths.count[w.get()] * (1.0 + beyond);
+                *along.entry(e).or_insert(0.0) += share;
+                *past.entry(v.get()).or_insert(0.0) += share;
+            }
+            if w.get() != s.get() {
+                *through.get_mut(w.get()).unwrap() += beyond;
+            }
+        }
+    }
+    // undirected paths are found once from each end
+    let scale = if D::DIRECTED { 1.0 } else { 0.5 };
+    through.values_mut().for_each(|t| *t *= scale);
+    let edges = edge_list(g).into_iter().map(|er| {
+        let score = along.get(&(er.1 as *const E)).map_or(0.0, |s| s * scale);
+        (er, score)
+    }).collect();
+    (through, edges)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Distance
+///////////////////////////////////////////////////////////////////////////////
+
+/// Inverse of the average distance to every vertex reachable from each one,
+///  scaled by the fraction of the graph it reaches so that a vertex with few
+///  close neighbors doesn't outrank one that reaches everything
+pub(crate) fn closeness<'a, V, E, D, W, S>(g: &'a Graph<V,E,D>, settle: S) -> HashMap<&'a V, f64>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>, W: NumT, S: Fn(&'a Vertex<V,E,D>) -> Settled<'a,V,E,D,W>
+{
+    let n = g.order();
+    g.vertices().map(|v| {
+        let paths = settle(v);
+        let reached = paths.dist.len() as f64 - 1.0;
+        let total: f64 = paths.dist.values().map(|d| d.to_f64()).sum();
+        let score = if total > 0.0 { reached / total * reached / (n - 1) as f64 } else { 0.0 };
+        (v.get(), score)
+    }).collect()
+}
+
+/// Sum of the inverse distances to every other vertex, which counts the
+///  unreachable ones as infinitely far. Weights must be positive
+pub(crate) fn harmonic<'a, V, E, D, W, S>(g: &'a Graph<V,E,D>, settle: S) -> HashMap<&'a V, f64>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>, W: NumT, S: Fn(&'a Vertex<V,E,D>) -> Settled<'a,V,E,D,W>
+{
+    g.vertices().map(|v| {
+        let paths = settle(v);
+        let score = paths.dist.iter()
+            .filter(|&(&u, _)| u != v.get())
+            .map(|(_, d)| 1.0 / d.to_f64())
+            .sum();
+        (v.get(), score)
+    }).collect()
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Spectral
+///////////////////////////////////////////////////////////////////////////////
+
+/// Scores proportional to the (weighted) sum of the scores of the vertices
+///  with edges to each, i.e. the principal eigenvector, by power iteration;
+///  scaled so their squares sum to one. Iterating on `A + I` rather than `A`
+///  keeps it from oscillating on bipartite graphs
+pub(crate) fn eigenvector<V, E, D, F>(g: &Graph<V,E,D>, weight: F) -> HashMap<&V, f64>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>, F: Fn(&E) -> f64
+{
+    let (verts, out) = adjacency(g, weight);
+    let n = verts.len();
+    let mut x = vec![1.0 / n as f64; n];
+    for _ in 0 .. MAX_ITERATIONS {
+        let mut next = x.clone();
+        for (i, edges) in out.iter().enumerate() {
+            for &(j, w) in edges {
+                next[j] += w * x[i];
+            }
+        }
+        normalize(&mut next, true);
+        let done = change(&x, &next) < TOLERANCE;
+        x = next;
+        if done { break }
+    }
+    by_vertex(&verts, x)
+}
+
+/// Each score is one plus `alpha` times the (weighted) sum of the scores of
+///  the vertices with edges to it; `None` if `alpha` is too large for that
+///  to settle (at least the inverse of the largest eigenvalue)
+pub(crate) fn katz<V, E, D, F>(g: &Graph<V,E,D>, alpha: f64, weight: F) -> Option<HashMap<&V, f64>>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>, F: Fn(&E) -> f64
+{
+    let (verts, out) = adjacency(g, weight);
+    let n = verts.len();
+    let mut x = vec![0.0; n];
+    for _ in 0 .. MAX_ITERATIONS {
+        let mut next = vec![1.0; n];
+        for (i, edges) in out.iter().enumerate() {
+            for &(j, w) in edges {
+                next[j] += alpha * w * x[i];
+            }
+        }
+        let diff = change(&x, &next);
+        if diff.is_finite() == false { return None }
+        x = next;
+        if diff < TOLERANCE * n as f64 {
+            return Some(by_vertex(&verts, x));
+        }
+    }
+    None
+}
+
+/// Hub and authority scores: good hubs have edges to good authorities and
+///  good authorities have edges from good hubs. Each set sums to one
+pub(crate) fn hits<V, E, D, F>(g: &Graph<V,E,D>, weight: F) -> (HashMap<&V, f64>, HashMap<&V, f64>)
+    where V: NodeT, E: EdgeT, D: DirT<V,E>, F: Fn(&E) -> f64
+{
+    let (verts, out) = adjacency(g, weight);
+    let n = verts.len();
+    let mut hubs = vec![1.0 / n as f64; n];
+    let mut auths = vec![0.0; n];
+    for _ in 0 .. MAX_ITERATIONS {
+        auths.iter_mut().for_each(|a| *a = 0.0);
+        for (i, edges) in out.iter().enumerate() {
+            for &(j, w) in edges {
+                auths[j] += w * hubs[i];
+            }
+        }
+        normalize(&mut auths, false);
+        let mut next: Vec<f64> = out.iter()
+            .map(|edges| edges.iter().map(|&(j, w)| w * auths[j]).sum())
+            .collect();
+        normalize(&mut next, false);
+        let done = change(&hubs, &next) < TOLERANCE;
+        hubs = next;
+        if done { break }
+    }
+    (by_vertex(&verts, hubs), by_vertex(&verts, auths))
+}
@@ -0,0 +1,118 @@
+
+use UndirectedGraph;
+use dir::Undir;
+use edge::EdgeT;
+use vertex::{NodeT, Vertex};
+use super::Neighborhoods;
+
+type UVertex<V,E> = Vertex<V, E, Undir<V,E>>;
+
+///////////////////////////////////////////////////////////////////////////////
+//  Bron–Kerbosch
+///////////////////////////////////////////////////////////////////////////////
+
+/// One call of Bron–Kerbosch: extend `clique` by the vertices of `cands`,
+///  without reporting a clique that could also take one from `excluded`
+struct Frame {
+    clique: Vec<usize>,
+    cands: Vec<usize>,
+    excluded: Vec<usize>,
+    // candidates not adjacent to the pivot, which are the only ones branched on
+    todo: Vec<usize>,
+}
+
+/// Every maximal clique of an undirected graph (each complete subgraph that
+///  no other vertex could join), by Bron–Kerbosch with pivoting. The outer
+///  level follows a degeneracy order, which keeps the search near O(d n 3^(d/3))
+///  for a graph of degeneracy `d`
+pub struct MaximalCliques<'a, V: 'a+NodeT, E: 'a+EdgeT> {
+    graph: Neighborhoods<'a,V,E>,
+    order: Vec<usize>,
+    // position of each vertex in `order`
+    rank: Vec<usize>,
+    next: usize,
+    stack: Vec<Frame>,
+}
+
+impl<'a, V: NodeT, E: EdgeT> MaximalCliques<'a,V,E> {
+    pub(crate) fn new(g: &'a UndirectedGraph<V,E>) -> Self {
+        let graph = Neighborhoods::new(g);
+        let order = graph.degeneracy_order();
+        let mut rank = vec![0; order.len()];
+        for (r, &i) in order.iter().enumerate() {
+            rank[i] = r;
+        }
+        MaximalCliques { graph, order, rank, next: 0, stack: vec![] }
+    }
+
+    /// Start a call, or report `clique` straight away if there's nothing to
+    ///  extend it with and it's maximal
+    fn call(&mut self, clique: Vec<usize>, cands: Vec<usize>, excluded: Vec<usize>)
+        -> Option<Vec<&'a UVertex<V,E>>>
+    {
+        if cands.is_empty() {
+            return if excluded.is_empty() {
+                Some(clique.into_iter().map(|i| self.graph.verts[i]).collect())
+            } else {
+                None
+            };
+        }
+        // the pivot with the most candidates for neighbors leaves the fewest branches
+        let hoods = &self.graph.hoods;
+        let pivot = cands.iter().chain(excluded.iter())
+            .max_by_key(|&&u| cands.iter().filter(|c| hoods[u].contains(c)).count())
+            .cloned()
+            .expect("there's at least one candidate");
+        let todo = cands.iter().cloned().filter(|c| hoods[pivot].contains(c) == false).collect();
+        self.stack.push(Frame { clique, cands, excluded, todo });
+        None
+    }
+}
+
+impl<'a, V: NodeT, E: EdgeT> Iterator for MaximalCliques<'a,V,E> {
+    type Item = Vec<&'a UVertex<V,E>>;
+    fn next(&mut self) -> Option<Vec<&'a UVertex<V,E>>> {
+        loop {
+            let (clique, cands, excluded) = match self.stack.last_mut() {
+                Some(frame) => {
+                    let v = match frame.todo.pop() {
+                        Some(v) => v,
+                        None => {
+                            self.stack.pop();
+                            continue;
+                        }
+                    };
+                    let hood = &self.graph.hoods[v];
+                    let cands = frame.cands.iter().cloned().filter(|u| hood.contains(u)).collect();
+                    let excluded = frame.excluded.iter().cloned().filter(|u| hood.contains(u)).collect();
+                    // cliques with `v` are found by the call below, so later
+                    //  branches of this one leave it out
+                    frame.cands.retain(|&u| u != v);
+                    frame.excluded.push(v);
+                    let mut clique = frame.clique.clone();
+                    clique.push(v);
+                    (clique, cands, excluded)
+                }
+                None => {
+                    // each clique is found from whichever of its vertices comes
+                    //  first in the order
+                    let &v = self.order.get(self.next)?;
+                    self.next += 1;
+                    let rank = &self.rank;
+                    let (later, earlier) = self.graph.hoods[v].iter().partition(|&&u| rank[u] > rank[v]);
+                    (vec![v], later, earlier)
+                }
+            };
+            if let Some(found) = self.call(clique, cands, excluded) {
+                return Some(found);
+            }
+        }
+    }
+}
+
+/// Largest clique by picking the biggest maximal one
+pub(crate) fn maximum_clique<V, E>(g: &UndirectedGraph<V,E>) -> Vec<&UVertex<V,E>>
+    where V: NodeT, E: EdgeT
+{
+    MaximalCliques::new(g).fold(vec![], |best, c| if c.len() > best.len() { c } else { best })
+}
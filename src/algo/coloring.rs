@@ -0,0 +1,160 @@
+
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+
+use UndirectedGraph;
+use edge::EdgeT;
+use vertex::NodeT;
+use super::Neighborhoods;
+use super::clique::maximum_clique;
+
+const NIL: usize = usize::MAX;
+
+/// The order in which a greedy coloring visits the vertices
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorOrder {
+    /// Highest degree first
+    LargestFirst,
+    /// Reverse of repeatedly removing a vertex of least remaining degree, so
+    ///  each vertex has at most `d` neighbors colored before it in a graph of
+    ///  degeneracy `d` and at most `d + 1` colors are used
+    SmallestLast,
+}
+
+/// Lowest color that none of `hood` has
+fn lowest_free(hood: &HashSet<usize>, colors: &[usize]) -> usize {
+    let taken: HashSet<usize> = hood.iter().map(|&u| colors[u]).collect();
+    // there are only so many neighbors to take colors
+    (0 ..= hood.len()).find(|c| taken.contains(c) == false).expect("some color is free")
+}
+
+fn by_vertex<'a, V, E>(hoods: &Neighborhoods<'a,V,E>, colors: Vec<usize>) -> HashMap<&'a V, usize>
+    where V: NodeT, E: EdgeT
+{
+    hoods.verts.iter().zip(colors).map(|(v, c)| (v.get(), c)).collect()
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Heuristics
+///////////////////////////////////////////////////////////////////////////////
+
+/// Give each vertex in turn the lowest color its neighbors don't have
+pub(crate) fn greedy<V, E>(g: &UndirectedGraph<V,E>, order: ColorOrder) -> HashMap<&V, usize>
+    where V: NodeT, E: EdgeT
+{
+    let hoods = Neighborhoods::new(g);
+    let order = match order {
+        ColorOrder::LargestFirst => {
+            let mut order: Vec<usize> = (0 .. hoods.verts.len()).collect();
+            order.sort_by_key(|&i| cmp::Reverse(hoods.hoods[i].len()));
+            order
+        }
+        ColorOrder::SmallestLast => {
+            let mut order = hoods.degeneracy_order();
+            order.reverse();
+            order
+        }
+    };
+    let mut colors = vec![NIL; hoods.verts.len()];
+    for i in order {
+        colors[i] = lowest_free(&hoods.hoods[i], &colors);
+    }
+    by_vertex(&hoods, colors)
+}
+
+/// Colors given greedily to the vertex whose neighbors already have the most
+///  distinct colors (its saturation), breaking ties by degree
+fn dsatur_colors<V, E>(hoods: &Neighborhoods<'_,V,E>) -> Vec<usize>
+    where V: NodeT, E: EdgeT
+{
+    let n = hoods.verts.len();
+    let mut colors = vec![NIL; n];
+    let mut saturation: Vec<HashSet<usize>> = vec![HashSet::new(); n];
+    for _ in 0 .. n {
+        let i = (0 .. n).filter(|&i| colors[i] == NIL)
+            .max_by_key(|&i| (saturation[i].len(), hoods.hoods[i].len()))
+            .expect("a vertex is left uncolored");
+        let c = lowest_free(&hoods.hoods[i], &colors);
+        colors[i] = c;
+        for &j in &hoods.hoods[i] {
+            saturation[j].insert(c);
+        }
+    }
+    colors
+}
+
+pub(crate) fn dsatur<V, E>(g: &UndirectedGraph<V,E>) -> HashMap<&V, usize>
+    where V: NodeT, E: EdgeT
+{
+    let hoods = Neighborhoods::new(g);
+    let colors = dsatur_colors(&hoods);
+    by_vertex(&hoods, colors)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Branch and bound
+///////////////////////////////////////////////////////////////////////////////
+
+/// Exact coloring by DSatur-ordered backtracking; any coloring needs as many
+///  colors as the largest clique, so the search stops once it gets that low
+struct Exact<'h> {
+    hoods: &'h [HashSet<usize>],
+    colors: Vec<usize>,
+    best: Vec<usize>,
+    best_count: usize,
+    lower: usize,
+}
+
+impl<'h> Exact<'h> {
+    /// Extend the partial coloring, which uses `used` colors; true once a
+    ///  coloring meeting the lower bound has been found
+    fn branch(&mut self, used: usize) -> bool {
+        let hoods = self.hoods;
+        let saturation = |i: usize, colors: &[usize]| {
+            hoods[i].iter().map(|&j| colors[j]).filter(|&c| c != NIL).collect::<HashSet<_>>().len()
+        };
+        let next = (0 .. hoods.len()).filter(|&i| self.colors[i] == NIL)
+            .max_by_key(|&i| (saturation(i, &self.colors), hoods[i].len()));
+        let i = match next {
+            Some(i) => i,
+            None => {
+                self.best = self.colors.clone();
+                self.best_count = used;
+                return used == self.lower;
+            }
+        };
+        // a new color is only worth trying if it still beats the best so far
+        for c in 0 ..= used {
+            if used.max(c + 1) >= self.best_count { break }
+            if hoods[i].iter().any(|&j| self.colors[j] == c) { continue }
+            self.colors[i] = c;
+            if self.branch(used.max(c + 1)) { return true }
+        }
+        self.colors[i] = NIL;
+        false
+    }
+}
+
+/// A coloring with as few colors as possible; exponential in the worst case
+pub(crate) fn exact<V, E>(g: &UndirectedGraph<V,E>) -> HashMap<&V, usize>
+    where V: NodeT, E: EdgeT
+{
+    let hoods = Neighborhoods::new(g);
+    let best = dsatur_colors(&hoods);
+    let best_count = best.iter().max().map_or(0, |&c| c + 1);
+    let index: HashMap<&V, usize> = hoods.verts.iter().enumerate().map(|(i, v)| (v.get(), i)).collect();
+    let clique: Vec<usize> = maximum_clique(g).into_iter().map(|v| index[v.get()]).collect();
+    if clique.len() == best_count {
+        return by_vertex(&hoods, best);
+    }
+    // the clique's colors are forced up to renaming, so fixing them first
+    //  skips that many permutations of every coloring
+    let mut colors = vec![NIL; hoods.verts.len()];
+    for (c, &i) in clique.iter().enumerate() {
+        colors[i] = c;
+    }
+    let mut search = Exact { hoods: &hoods.hoods, colors, best, best_count, lower: clique.len() };
+    search.branch(clique.len());
+    let best = search.best;
+    by_vertex(&hoods, best)
+}
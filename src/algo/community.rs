@@ -0,0 +1,240 @@
+
+use std::collections::{BTreeMap, HashMap};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use UndirectedGraph;
+use dir::Undir;
+use edge::{WeightT, NumT};
+use vertex::{NodeT, Vertex};
+
+type UVertex<V,E> = Vertex<V, E, Undir<V,E>>;
+
+/// Gains smaller than this are rounding error, and chasing them could loop
+const EPSILON: f64 = 1e-12;
+/// Label propagation almost always settles in a handful of sweeps
+const MAX_SWEEPS: usize = 1000;
+
+///////////////////////////////////////////////////////////////////////////////
+//  Communities
+///////////////////////////////////////////////////////////////////////////////
+
+/// A partition of the vertices alongside its modularity: the fraction of
+///  edge weight inside communities minus what's expected if edges were
+///  rewired at random keeping every degree, from -1/2 up to 1
+#[derive(Debug)]
+pub struct Communities<'a, V: 'a+NodeT, E: 'a+WeightT> {
+    communities: Vec<Vec<&'a UVertex<V,E>>>,
+    modularity: f64,
+}
+
+impl<'a, V: NodeT, E: WeightT> Communities<'a,V,E> {
+    pub fn communities(&self) -> &[Vec<&'a UVertex<V,E>>] {
+        &self.communities
+    }
+    pub fn into_communities(self) -> Vec<Vec<&'a UVertex<V,E>>> {
+        self.communities
+    }
+    pub fn modularity(&self) -> f64 {
+        self.modularity
+    }
+    /// Index into `communities()` of the one containing `v`
+    pub fn community_of(&self, v: &V) -> Option<usize> {
+        self.communities.iter().position(|c| c.iter().any(|u| u.get() == v))
+    }
+}
+
+/// Vertices numbered with weighted neighbors. Edges are listed from both ends
+///  as in the graph, so the weights listed for a vertex sum to its degree and
+///  a self-loop counts twice. The numbering sorts vertices by a hash with
+///  fixed keys rather than the graph's own, so the same graph always gets
+///  the same numbering and so the same communities
+struct Weights<'a, V: 'a+NodeT, E: 'a+WeightT> {
+    verts: Vec<&'a UVertex<V,E>>,
+    adj: Vec<Vec<(usize, f64)>>,
+}
+
+impl<'a, V: NodeT, E: WeightT> Weights<'a,V,E> {
+    fn new(g: &'a UndirectedGraph<V,E>) -> Self {
+        let mut verts: Vec<_> = g.vertices().collect();
+        verts.sort_by_cached_key(|v| {
+            let mut hasher = DefaultHasher::new();
+            v.get().hash(&mut hasher);
+            hasher.finish()
+        });
+        let index: HashMap<&V, usize> = verts.iter().enumerate().map(|(i, v)| (v.get(), i)).collect();
+        let adj = verts.iter().map(|&v| {
+            g.get_neighbors(v).with_edges().map(|(n, e)| (index[n.get()], e.weight().to_f64())).collect()
+        }).collect();
+        Weights { verts, adj }
+    }
+
+    /// Group the vertices by label and score the grouping
+    fn communities(self, label: &[usize]) -> Communities<'a,V,E> {
+        let modularity = modularity(&self.adj, label);
+        let mut groups: BTreeMap<usize, Vec<&UVertex<V,E>>> = BTreeMap::new();
+        for (i, v) in self.verts.into_iter().enumerate() {
+            groups.entry(label[i]).or_default().push(v);
+        }
+        Communities { communities: groups.into_values().collect(), modularity }
+    }
+}
+
+fn degrees(adj: &[Vec<(usize, f64)>]) -> Vec<f64> {
+    adj.iter().map(|hood| hood.iter().map(|&(_, w)| w).sum()).collect()
+}
+
+/// Σ over communities of (internal weight / m) - (total degree / 2m)²
+fn modularity(adj: &[Vec<(usize, f64)>], label: &[usize]) -> f64 {
+    let degree = degrees(adj);
+    let two_m: f64 = degree.iter().sum();
+    if two_m <= 0.0 { return 0.0 }
+    let mut inside: BTreeMap<usize, f64> = BTreeMap::new();
+    let mut total: BTreeMap<usize, f64> = BTreeMap::new();
+    for (i, hood) in adj.iter().enumerate() {
+        *total.entry(label[i]).or_insert(0.0) += degree[i];
+        for &(j, w) in hood {
+            // twice the weight inside, since edges are listed from both ends
+            if label[i] == label[j] {
+                *inside.entry(label[i]).or_insert(0.0) += w;
+            }
+        }
+    }
+    total.iter().map(|(c, &d)| {
+        inside.get(c).cloned().unwrap_or(0.0) / two_m - (d / two_m) * (d / two_m)
+    }).sum()
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Louvain
+///////////////////////////////////////////////////////////////////////////////
+
+/// Move single vertices to whichever neighboring community raises modularity
+///  most until none does; returns the community of each and whether any moved
+fn local_moves(adj: &[Vec<(usize, f64)>]) -> (Vec<usize>, bool) {
+    let n = adj.len();
+    let degree = degrees(adj);
+    let two_m: f64 = degree.iter().sum();
+    let mut label: Vec<usize> = (0 .. n).collect();
+    let mut total = degree.clone();
+    let mut moved_any = false;
+    loop {
+        let mut moved = false;
+        for i in 0 .. n {
+            let here = label[i];
+            total[here] -= degree[i];
+            // in label order, so ties go to the lowest label
+            let mut towards: BTreeMap<usize, f64> = BTreeMap::new();
+            towards.insert(here, 0.0);
+            for &(j, w) in adj[i].iter().filter(|&&(j, _)| j != i) {
+                *towards.entry(label[j]).or_insert(0.0) += w;
+            }
+            // joining `c` gains (weight to `c`) - (degree of `c`)(degree of `i`)/2m,
+            //  up to a factor that's the same for every `c`
+            let gain = |c: usize, w: f64| w - total[c] * degree[i] / two_m;
+            let mut best = (here, gain(here, towards[&here]));
+            for (&c, &w) in &towards {
+                let g = gain(c, w);
+                if g > best.1 + EPSILON {
+                    best = (c, g);
+                }
+            }
+            label[i] = best.0;
+            total[best.0] += degree[i];
+            if best.0 != here {
+                moved = true;
+            }
+        }
+        if moved == false { break }
+        moved_any = true;
+    }
+    (label, moved_any)
+}
+
+/// Contract each community to a vertex, with a self-loop for the weight inside
+///  it (listed once with both ends' share); returns the new graph and the
+///  vertex each old one became
+fn aggregate(adj: &[Vec<(usize, f64)>], label: &[usize]) -> (Vec<Vec<(usize, f64)>>, Vec<usize>) {
+    let mut renumber: HashMap<usize, usize> = HashMap::new();
+    let into: Vec<usize> = label.iter().map(|&l| {
+        let next = renumber.len();
+        *renumber.entry(l).or_insert(next)
+    }).collect();
+    let mut weights: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); renumber.len()];
+    for (i, hood) in adj.iter().enumerate() {
+        for &(j, w) in hood {
+            *weights[into[i]].entry(into[j]).or_insert(0.0) += w;
+        }
+    }
+    let adj = weights.into_iter().map(|hood| hood.into_iter().collect()).collect();
+    (adj, into)
+}
+
+/// Louvain: alternately move vertices between communities while modularity
+///  rises and contract each community to a single vertex, until nothing moves.
+///  Edge weights should be positive
+pub(crate) fn louvain<V, E>(g: &UndirectedGraph<V,E>) -> Communities<'_,V,E>
+    where V: NodeT, E: WeightT
+{
+    let weights = Weights::new(g);
+    let mut label: Vec<usize> = (0 .. weights.verts.len()).collect();
+    let mut adj = weights.adj.clone();
+    loop {
+        let (moves, moved) = local_moves(&adj);
+        if moved == false { break }
+        let (next, into) = aggregate(&adj, &moves);
+        for l in &mut label {
+            *l = into[*l];
+        }
+        adj = next;
+    }
+    weights.communities(&label)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Label propagation
+///////////////////////////////////////////////////////////////////////////////
+
+/// Linear congruential generator, since only shuffles and tie-breaks need it
+struct Lcg(u64);
+
+impl Lcg {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 33) % n as u64) as usize
+    }
+}
+
+/// Asynchronous label propagation: visiting vertices in a random order, each
+///  takes the label with the most weight among its neighbors (keeping its own
+///  if that's tied for the most) until none changes. `seed` drives the order
+///  and the tie-breaks, so different seeds can give different partitions
+pub(crate) fn label_propagation<V, E>(g: &UndirectedGraph<V,E>, seed: u64) -> Communities<'_,V,E>
+    where V: NodeT, E: WeightT
+{
+    let weights = Weights::new(g);
+    let n = weights.verts.len();
+    let mut rng = Lcg(seed);
+    let mut label: Vec<usize> = (0 .. n).collect();
+    let mut order: Vec<usize> = (0 .. n).collect();
+    for _ in 0 .. MAX_SWEEPS {
+        for i in (1 .. n).rev() {
+            order.swap(i, rng.below(i + 1));
+        }
+        let mut changed = false;
+        for &i in &order {
+            let mut votes: BTreeMap<usize, f64> = BTreeMap::new();
+            for &(j, w) in weights.adj[i].iter().filter(|&&(j, _)| j != i) {
+                *votes.entry(label[j]).or_insert(0.0) += w;
+            }
+            if votes.is_empty() { continue }
+            let most = votes.values().cloned().fold(0.0, f64::max);
+            let best: Vec<usize> = votes.into_iter().filter(|&(_, w)| w >= most - EPSILON).map(|(l, _)| l).collect();
+            if best.contains(&label[i]) { continue }
+            label[i] = best[rng.below(best.len())];
+            changed = true;
+        }
+        if changed == false { break }
+    }
+    weights.communities(&label)
+}
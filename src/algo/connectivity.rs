@@ -0,0 +1,138 @@
+
+use std::cmp;
+use std::ptr;
+use std::collections::{HashMap, HashSet};
+
+use {Graph, DiGraph, UndirectedGraph, UnweightedEdge};
+use dir::Undir;
+use edge::EdgeT;
+use vertex::{NodeT, Vertex};
+use super::EdgeRef;
+
+type UVertex<V,E> = Vertex<V, E, Undir<V,E>>;
+type UEdgeRef<'a,V,E> = EdgeRef<'a, V, E, Undir<V,E>>;
+
+///////////////////////////////////////////////////////////////////////////////
+//  Condensation
+///////////////////////////////////////////////////////////////////////////////
+
+/// Contract each strong component to one vertex (the list of its members);
+///  the result is always acyclic
+pub(crate) fn condensation<V, E>(g: &DiGraph<V,E>) -> DiGraph<Vec<&V>, UnweightedEdge>
+    where V: NodeT, E: EdgeT
+{
+    let components: Vec<Vec<&V>> = g.strong_components()
+        .map(|c| c.into_iter().map(|v| v.get()).collect())
+        .collect();
+    let mut which: HashMap<&V, usize> = HashMap::with_capacity(g.order());
+    for (i, c) in components.iter().enumerate() {
+        which.extend(c.iter().map(|&v| (v, i)));
+    }
+    let mut dag = Graph::with_capacity(components.len(), 0);
+    for c in &components {
+        dag.insert_vertex(c.clone());
+    }
+    let mut joined = HashSet::new();
+    for src in g.vertices() {
+        let i = which[src.get()];
+        for dst in g.get_children(src) {
+            let j = which[dst.get()];
+            if i != j && joined.insert((i, j)) {
+                dag.insert_directed_edge(UnweightedEdge, &components[i], &components[j]);
+            }
+        }
+    }
+    dag
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Low-link analysis
+///////////////////////////////////////////////////////////////////////////////
+
+/// Everything Hopcroft-Tarjan's depth-first search finds in one pass
+#[derive(Debug)]
+pub(crate) struct LowLink<'a, V: 'a+NodeT, E: 'a+EdgeT> {
+    /// Edges whose removal disconnects their endpoints
+    pub bridges: Vec<UEdgeRef<'a,V,E>>,
+    /// Vertices whose removal disconnects their component
+    pub cut_vertices: Vec<&'a UVertex<V,E>>,
+    /// Maximal 2-connected subgraphs, as edge sets; every edge is in exactly one
+    pub blocks: Vec<Vec<UEdgeRef<'a,V,E>>>,
+}
+
+pub(crate) fn low_link<V, E>(g: &UndirectedGraph<V,E>) -> LowLink<'_,V,E>
+    where V: NodeT, E: EdgeT
+{
+    let mut disc: HashMap<&V, usize> = HashMap::with_capacity(g.order());
+    let mut low: HashMap<&V, usize> = HashMap::with_capacity(g.order());
+    let mut cuts: HashSet<&V> = HashSet::new();
+    let mut result = LowLink { bridges: vec![], cut_vertices: vec![], blocks: vec![] };
+    // edges of the blocks still being explored
+    let mut edges: Vec<UEdgeRef<V,E>> = vec![];
+
+    for root in g.vertices() {
+        if disc.contains_key(root.get()) { continue }
+        disc.insert(root.get(), disc.len());
+        low.insert(root.get(), disc[root.get()]);
+        let mut root_children = 0;
+        // (vertex, edge to its parent, unexplored neighbors)
+        let mut calls: Vec<(&UVertex<V,E>, Option<(&UVertex<V,E>, &E)>, _)> =
+            vec![(root, None, g.get_neighbors(root).with_edges())];
+
+        while let Some((v, up, next)) = calls.last_mut().map(|c| (c.0, c.1, c.2.next())) {
+            match next {
+                Some((_, e)) if up.is_some_and(|(_, up)| ptr::eq(e, up)) => {}
+                Some((w, e)) if w.get() == v.get() => {
+                    // a self-loop is a block on its own, seen once from each end
+                    if result.blocks.iter().all(|b| ptr::eq(b[0].1, e) == false) {
+                        result.blocks.push(vec![(v, e, w)]);
+                    }
+                }
+                Some((w, e)) => match disc.get(w.get()).cloned() {
+                    None => {
+                        edges.push((v, e, w));
+                        disc.insert(w.get(), disc.len());
+                        low.insert(w.get(), disc[w.get()]);
+                        calls.push((w, Some((v, e)), g.get_neighbors(w).with_edges()));
+                    }
+                    // back edge to an ancestor; seen again from that end later
+                    Some(dw) if dw < disc[v.get()] => {
+                        edges.push((v, e, w));
+                        let lv = low.get_mut(v.get()).unwrap();
+                        *lv = cmp::min(*lv, dw);
+                    }
+                    Some(_) => {}
+                },
+                None => {
+                    calls.pop();
+                    let (u, e) = match up {
+                        Some(up) => up,
+                        None => continue,
+                    };
+                    let lv = low[v.get()];
+                    let du = disc[u.get()];
+                    let lu = low.get_mut(u.get()).unwrap();
+                    *lu = cmp::min(*lu, lv);
+                    if lv >= du {
+                        // everything explored since `e` is cut off by `u`
+                        let i = edges.iter().rposition(|x| ptr::eq(x.1, e)).unwrap();
+                        result.blocks.push(edges.split_off(i));
+                        if calls.len() > 1 {
+                            cuts.insert(u.get());
+                        } else {
+                            root_children += 1;
+                        }
+                    }
+                    if lv > du {
+                        result.bridges.push((u, e, v));
+                    }
+                }
+            }
+        }
+        if root_children > 1 {
+            cuts.insert(root.get());
+        }
+    }
+    result.cut_vertices = g.vertices().filter(|v| cuts.contains(v.get())).collect();
+    result
+}
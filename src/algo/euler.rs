@@ -0,0 +1,125 @@
+
+use std::collections::{HashMap, HashSet};
+
+use {Graph, UndirectedGraph, DiGraph};
+use dir::{DirT, Dir, Undir};
+use edge::EdgeT;
+use path::{Path, Cycle};
+use vertex::{NodeT, Vertex};
+
+///////////////////////////////////////////////////////////////////////////////
+//  Degree conditions
+///////////////////////////////////////////////////////////////////////////////
+
+/// Where the degrees say a trail through every edge has to start
+pub(crate) enum Ends<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>> {
+    /// Every vertex is balanced, so the trail is closed and can start anywhere
+    Closed,
+    /// The trail starts here and ends at the only other unbalanced vertex
+    Open(&'a Vertex<V,E,D>),
+    /// Too many vertices are unbalanced for any trail
+    Impossible,
+}
+
+/// A trail needs every vertex but its ends to have even degree
+pub(crate) fn undirected_ends<V, E>(g: &UndirectedGraph<V,E>) -> Ends<'_, V, E, Undir<V,E>>
+    where V: NodeT, E: EdgeT
+{
+    let odd: Vec<_> = g.vertices().filter(|v| v.degree() % 2 == 1).take(3).collect();
+    match odd.len() {
+        0 => Ends::Closed,
+        2 => Ends::Open(odd[0]),
+        _ => Ends::Impossible,
+    }
+}
+
+/// A trail needs every vertex but its ends to have as many edges in as out;
+///  it starts with one extra out and ends with one extra in
+pub(crate) fn directed_ends<V, E>(g: &DiGraph<V,E>) -> Ends<'_, V, E, Dir<V,E>>
+    where V: NodeT, E: EdgeT
+{
+    let (mut start, mut end) = (None, None);
+    for v in g.vertices() {
+        let (i, o) = (v.in_degree(), v.out_degree());
+        if o == i + 1 && start.is_none() {
+            start = Some(v);
+        } else if i == o + 1 && end.is_none() {
+            end = Some(v);
+        } else if i != o {
+            return Ends::Impossible;
+        }
+    }
+    match (start, end) {
+        (None, None) => Ends::Closed,
+        (Some(s), Some(_)) => Ends::Open(s),
+        _ => Ends::Impossible,
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Hierholzer
+///////////////////////////////////////////////////////////////////////////////
+
+/// A closed walk using every edge exactly once
+pub(crate) fn eulerian_circuit<'a, V, E, D>(g: &'a Graph<V,E,D>, ends: Ends<'a,V,E,D>)
+    -> Option<Cycle<'a,V,E,D>>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>
+{
+    match ends {
+        Ends::Closed => hierholzer(g, any_start(g)?).map(Cycle::new),
+        _ => None,
+    }
+}
+
+/// A walk using every edge exactly once, closed if it can be
+pub(crate) fn eulerian_path<'a, V, E, D>(g: &'a Graph<V,E,D>, ends: Ends<'a,V,E,D>)
+    -> Option<Path<'a,V,E,D>>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>
+{
+    match ends {
+        Ends::Closed => hierholzer(g, any_start(g)?),
+        Ends::Open(start) => hierholzer(g, start),
+        Ends::Impossible => None,
+    }
+}
+
+/// A vertex with an edge if there is one, since isolated vertices don't
+///  need to be visited
+fn any_start<V, E, D>(g: &Graph<V,E,D>) -> Option<&Vertex<V,E,D>>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>
+{
+    g.vertices().find(|v| v.degree() > 0).or_else(|| g.vertices().next())
+}
+
+/// Follow unused edges from `start` until stuck, then splice in detours from
+///  each vertex on the way back; `None` if some edge was out of reach
+fn hierholzer<'a, V, E, D>(g: &'a Graph<V,E,D>, start: &'a Vertex<V,E,D>)
+    -> Option<Path<'a,V,E,D>>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>
+{
+    // undirected edges are listed at both ends, so they're marked by address
+    let mut used: HashSet<*const E> = HashSet::with_capacity(g.size());
+    // resumed where they left off, so each edge is only looked at twice
+    let mut hoods = HashMap::with_capacity(g.order());
+    let mut unused = |v: &'a Vertex<V,E,D>| {
+        hoods.entry(v.get())
+            .or_insert_with(|| g.get_reachable(v).with_edges())
+            .find(|&(_, e)| used.insert(e))
+    };
+    // each vertex alongside the edge it was reached by
+    let mut stack: Vec<(&Vertex<V,E,D>, Option<&E>)> = vec![(start, None)];
+    let mut trail = Vec::with_capacity(g.size() + 1);
+    while let Some(&(v, _)) = stack.last() {
+        match unused(v) {
+            Some((next, edge)) => stack.push((next, Some(edge))),
+            None => trail.extend(stack.pop()),
+        }
+    }
+    if trail.len() != g.size() + 1 { return None }
+
+    let mut path = Path::new(start);
+    for (v, edge) in trail.into_iter().rev().skip(1) {
+        path.push(edge.expect("only the start has no edge"), v);
+    }
+    Some(path)
+}
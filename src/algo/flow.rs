@@ -0,0 +1,436 @@
+
+use std::cmp;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+use DiGraph;
+use dir::Dir;
+use edge::{EdgeT, CapacityT, CostT, NumT};
+use vertex::{NodeT, Vertex};
+use super::{edge_list, EdgeRef, MinFirst};
+
+type DVertex<V,E> = Vertex<V, E, Dir<V,E>>;
+type DEdgeRef<'a,V,E> = EdgeRef<'a, V, E, Dir<V,E>>;
+
+///////////////////////////////////////////////////////////////////////////////
+//  Residual network
+///////////////////////////////////////////////////////////////////////////////
+
+/// The graph re-indexed with a reverse arc alongside every edge, so flow
+///  can be pushed back without touching the caller's graph
+pub(crate) struct Residual<'a, V: 'a+NodeT, E: 'a+EdgeT, W> {
+    pub verts: Vec<&'a DVertex<V,E>>,
+    pub index: HashMap<&'a V, usize>,
+    pub edges: Vec<DEdgeRef<'a,V,E>>,
+    // arc `2k` follows edge `k` and arc `2k+1` runs against it
+    pub head: Vec<usize>,
+    pub room: Vec<W>,
+    pub cost: Vec<W>,
+    // may include vertices beyond `verts` that don't exist in the graph
+    pub out: Vec<Vec<usize>>,
+}
+
+impl<'a, V: NodeT, E: EdgeT, W: NumT> Residual<'a,V,E,W> {
+    pub fn new<C: Fn(&E) -> W>(g: &'a DiGraph<V,E>, capacity: C) -> Self {
+        let verts: Vec<_> = g.vertices().collect();
+        let index: HashMap<&V, usize> = verts.iter().enumerate().map(|(i, v)| (v.get(), i)).collect();
+        let edges = edge_list(g);
+        let m = 2 * edges.len();
+        let out = vec![vec![]; verts.len()];
+        let mut net = Residual {
+            verts, index, edges: vec![], out,
+            head: Vec::with_capacity(m), room: Vec::with_capacity(m), cost: Vec::with_capacity(m),
+        };
+        for &(l, e, r) in &edges {
+            let (l, r) = (net.index[l.get()], net.index[r.get()]);
+            net.add_arc(l, r, capacity(e), W::zero());
+        }
+        net.edges = edges;
+        net
+    }
+    pub fn set_costs<C: Fn(&E) -> W>(&mut self, cost: C) {
+        for (k, &(_, e, _)) in self.edges.iter().enumerate() {
+            self.cost[2 * k] = cost(e);
+            self.cost[2 * k + 1] = W::zero() - cost(e);
+        }
+    }
+    pub fn order(&self) -> usize {
+        self.out.len()
+    }
+    pub fn add_vertex(&mut self) -> usize {
+        self.out.push(vec![]);
+        self.out.len() - 1
+    }
+    pub fn add_arc(&mut self, l: usize, r: usize, capacity: W, cost: W) {
+        self.out[l].push(self.head.len());
+        self.head.push(r);
+        self.room.push(capacity);
+        self.cost.push(cost);
+        self.out[r].push(self.head.len());
+        self.head.push(l);
+        self.room.push(W::zero());
+        self.cost.push(W::zero() - cost);
+    }
+    pub fn tail(&self, arc: usize) -> usize {
+        self.head[arc ^ 1]
+    }
+    pub fn has_room(&self, arc: usize) -> bool {
+        self.room[arc] > W::zero()
+    }
+    pub fn push(&mut self, arc: usize, amount: W) {
+        self.room[arc] = self.room[arc] - amount;
+        self.room[arc ^ 1] = self.room[arc ^ 1] + amount;
+    }
+    /// Flow along edge `k`, which is however much its reverse arc could undo
+    pub fn flow(&self, k: usize) -> W {
+        self.room[2 * k + 1]
+    }
+    /// Breadth-first distances from `s` using only arcs with room left
+    pub fn levels(&self, s: usize) -> Vec<Option<usize>> {
+        let mut level = vec![None; self.order()];
+        let mut queue = VecDeque::new();
+        level[s] = Some(0);
+        queue.push_back(s);
+        while let Some(u) = queue.pop_front() {
+            for &a in &self.out[u] {
+                let v = self.head[a];
+                if level[v].is_none() && self.has_room(a) {
+                    level[v] = level[u].map(|l| l + 1);
+                    queue.push_back(v);
+                }
+            }
+        }
+        level
+    }
+    fn bottleneck(&self, path: &[usize]) -> W {
+        path.iter().map(|&a| self.room[a]).min().expect("empty augmenting path")
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Maximum flow
+///////////////////////////////////////////////////////////////////////////////
+
+/// A maximum flow and the minimum cut certifying it
+#[derive(Debug)]
+pub struct MaxFlow<'a, V: 'a+NodeT, E: 'a+EdgeT, W> {
+    value: W,
+    flows: Vec<(DEdgeRef<'a,V,E>, W)>,
+    by_addr: HashMap<*const E, usize>,
+    source_side: Vec<&'a DVertex<V,E>>,
+}
+
+impl<'a, V: NodeT, E: EdgeT, W: NumT> MaxFlow<'a,V,E,W> {
+    fn new(net: Residual<'a,V,E,W>, s: usize, value: W) -> Self {
+        let reached = net.levels(s);
+        let source_side = net.verts.iter().zip(reached)
+            .filter(|&(_, l)| l.is_some())
+            .map(|(&v, _)| v)
+            .collect();
+        let flows: Vec<_> = (0 .. net.edges.len()).map(|k| (net.edges[k], net.flow(k))).collect();
+        let by_addr = flows.iter().enumerate().map(|(k, f)| ((f.0).1 as *const E, k)).collect();
+        MaxFlow { value, flows, by_addr, source_side }
+    }
+
+    /// Total flow out of the source
+    pub fn value(&self) -> W {
+        self.value
+    }
+    /// Flow along `edge` (looked up by address, so it must be from the graph)
+    pub fn flow(&self, edge: &E) -> Option<W> {
+        self.by_addr.get(&(edge as *const E)).map(|&k| self.flows[k].1)
+    }
+    /// Every edge alongside the flow it carries
+    pub fn flows(&self) -> &[(DEdgeRef<'a,V,E>, W)] {
+        &self.flows
+    }
+    /// Vertices still reachable from the source in the residual graph; the
+    ///  edges leaving this set form a minimum cut
+    pub fn min_cut(&self) -> &[&'a DVertex<V,E>] {
+        &self.source_side
+    }
+}
+
+/// Augment along shortest paths found by breadth-first search; O(nm²)
+pub(crate) fn edmonds_karp<'a, V, E>(g: &'a DiGraph<V,E>,
+                                     source: &'a DVertex<V,E>,
+                                     sink: &'a DVertex<V,E>)
+    -> MaxFlow<'a, V, E, E::W>
+    where V: NodeT, E: CapacityT
+{
+    let mut net = Residual::new(g, E::capacity);
+    let (s, t) = (net.index[source.get()], net.index[sink.get()]);
+    let mut value = E::W::zero();
+    if s == t {
+        return MaxFlow::new(net, s, value);
+    }
+    loop {
+        let mut via: Vec<Option<usize>> = vec![None; net.order()];
+        let mut queue = VecDeque::new();
+        queue.push_back(s);
+        while let Some(u) = queue.pop_front() {
+            for &a in &net.out[u] {
+                let v = net.head[a];
+                if v != s && via[v].is_none() && net.has_room(a) {
+                    via[v] = Some(a);
+                    queue.push_back(v);
+                }
+            }
+            if via[t].is_some() { break }
+        }
+        if via[t].is_none() { break }
+
+        let mut path = vec![];
+        let mut cur = t;
+        while let Some(a) = via[cur] {
+            path.push(a);
+            cur = net.tail(a);
+        }
+        let push = net.bottleneck(&path);
+        for &a in &path {
+            net.push(a, push);
+        }
+        value = value + push;
+    }
+    MaxFlow::new(net, s, value)
+}
+
+/// Saturate the level graph in phases using blocking flows; O(n²m)
+pub(crate) fn dinic<'a, V, E>(g: &'a DiGraph<V,E>,
+                              source: &'a DVertex<V,E>,
+                              sink: &'a DVertex<V,E>)
+    -> MaxFlow<'a, V, E, E::W>
+    where V: NodeT, E: CapacityT
+{
+    let mut net = Residual::new(g, E::capacity);
+    let (s, t) = (net.index[source.get()], net.index[sink.get()]);
+    let mut value = E::W::zero();
+    if s == t {
+        return MaxFlow::new(net, s, value);
+    }
+    loop {
+        let mut level = net.levels(s);
+        if level[t].is_none() { break }
+        // next arc to try out of each vertex
+        let mut next = vec![0; net.order()];
+        let mut path: Vec<usize> = vec![];
+        let mut u = s;
+        loop {
+            if u == t {
+                let push = net.bottleneck(&path);
+                for &a in &path {
+                    net.push(a, push);
+                }
+                value = value + push;
+                path.clear();
+                u = s;
+                continue;
+            }
+            let advance = net.out[u][next[u]..].iter().position(|&a| {
+                let v = net.head[a];
+                net.has_room(a) && level[v].is_some() && level[v] == level[u].map(|l| l + 1)
+            });
+            match advance {
+                Some(i) => {
+                    next[u] += i;
+                    let a = net.out[u][next[u]];
+                    path.push(a);
+                    u = net.head[a];
+                }
+                None if u == s => break,
+                None => {
+                    // dead end: nothing more gets through `u` this phase
+                    next[u] = net.out[u].len();
+                    level[u] = None;
+                    let a = path.pop().expect("retreated past the source");
+                    u = net.tail(a);
+                    next[u] = cmp::min(next[u] + 1, net.out[u].len());
+                }
+            }
+        }
+    }
+    MaxFlow::new(net, s, value)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Minimum-cost flow
+///////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FlowError<W> {
+    /// Supplies and demands can't be balanced: only `routed` of the
+    ///  `required` units made it through the network
+    Infeasible { routed: W, required: W },
+}
+
+/// A flow alongside its total cost
+#[derive(Debug)]
+pub struct MinCostFlow<'a, V: 'a+NodeT, E: 'a+EdgeT, W> {
+    flow: MaxFlow<'a,V,E,W>,
+    cost: W,
+}
+
+impl<'a, V: NodeT, E: EdgeT, W: NumT> MinCostFlow<'a,V,E,W> {
+    /// Total flow routed
+    pub fn value(&self) -> W {
+        self.flow.value()
+    }
+    /// Sum over every edge of its flow times its cost
+    pub fn cost(&self) -> W {
+        self.cost
+    }
+    /// Flow along `edge` (looked up by address, so it must be from the graph)
+    pub fn flow(&self, edge: &E) -> Option<W> {
+        self.flow.flow(edge)
+    }
+    /// Every edge alongside the flow it carries
+    pub fn flows(&self) -> &[(DEdgeRef<'a,V,E>, W)] {
+        self.flow.flows()
+    }
+}
+
+/// Push flow around negative-cost cycles with room until there are none,
+///  which is the cheapest way to route nothing at all; returns the potentials
+///  Bellman-Ford finds once it settles and the cost of the flow pushed
+fn cancel_negative_cycles<V, E, W>(net: &mut Residual<V,E,W>) -> (Vec<W>, W)
+    where V: NodeT, E: EdgeT, W: NumT
+{
+    let n = net.order();
+    let mut cost = W::zero();
+    loop {
+        // starting every vertex at zero, as if from a source with an arc to each
+        let mut pi = vec![W::zero(); n];
+        let mut via: Vec<Option<usize>> = vec![None; n];
+        let mut last = None;
+        for _ in 0 .. n {
+            last = None;
+            for a in 0 .. net.head.len() {
+                if net.has_room(a) == false { continue }
+                let (u, v) = (net.tail(a), net.head[a]);
+                if pi[u] + net.cost[a] < pi[v] {
+                    pi[v] = pi[u] + net.cost[a];
+                    via[v] = Some(a);
+                    last = Some(v);
+                }
+            }
+            if last.is_none() { break }
+        }
+        let mut cur = match last {
+            Some(v) => v,
+            None => return (pi, cost),
+        };
+        // still relaxing after `n` rounds, so walking back `n` arcs lands on a cycle
+        for _ in 0 .. n {
+            cur = net.tail(via[cur].expect("relaxed vertex has no arc in"));
+        }
+        let mut cycle = vec![];
+        let start = cur;
+        loop {
+            let a = via[cur].expect("relaxed vertex has no arc in");
+            cycle.push(a);
+            cur = net.tail(a);
+            if cur == start { break }
+        }
+        let push = net.bottleneck(&cycle);
+        for &a in &cycle {
+            net.push(a, push);
+            cost = cost + push * net.cost[a];
+        }
+    }
+}
+
+/// Augment along cheapest paths, keeping reduced costs non-negative with
+///  vertex potentials so each path can be found by Dijkstra. Negative cycles
+///  are saturated first, since the cheapest flow of any value uses them fully
+fn successive_shortest_paths<V, E, W>(net: &mut Residual<V,E,W>, s: usize, t: usize) -> (W, W)
+    where V: NodeT, E: EdgeT, W: NumT
+{
+    let n = net.order();
+    let (mut pi, mut cost) = cancel_negative_cycles(net);
+    let mut value = W::zero();
+    loop {
+        let mut dist: Vec<Option<W>> = vec![None; n];
+        let mut via: Vec<Option<usize>> = vec![None; n];
+        let mut queue = BinaryHeap::new();
+        dist[s] = Some(W::zero());
+        queue.push(MinFirst(W::zero(), s));
+        while let Some(MinFirst(d, u)) = queue.pop() {
+            if dist[u].is_some_and(|du| du < d) { continue }
+            for &a in &net.out[u] {
+                if net.has_room(a) == false { continue }
+                let v = net.head[a];
+                let alt = d + net.cost[a] + pi[u] - pi[v];
+                if dist[v].is_none_or(|dv| alt < dv) {
+                    dist[v] = Some(alt);
+                    via[v] = Some(a);
+                    queue.push(MinFirst(alt, v));
+                }
+            }
+        }
+        if dist[t].is_none() { break }
+        for (p, d) in pi.iter_mut().zip(&dist) {
+            if let Some(d) = *d { *p = *p + d; }
+        }
+
+        let mut path = vec![];
+        let mut cur = t;
+        while let Some(a) = via[cur] {
+            path.push(a);
+            cur = net.tail(a);
+        }
+        let push = net.bottleneck(&path);
+        for &a in &path {
+            net.push(a, push);
+            cost = cost + push * net.cost[a];
+        }
+        value = value + push;
+    }
+    (value, cost)
+}
+
+/// The most flow from `source` to `sink`, routed as cheaply as possible
+pub(crate) fn min_cost_max_flow<'a, V, E>(g: &'a DiGraph<V,E>,
+                                          source: &'a DVertex<V,E>,
+                                          sink: &'a DVertex<V,E>)
+    -> MinCostFlow<'a, V, E, E::W>
+    where V: NodeT, E: CostT
+{
+    let mut net = Residual::new(g, E::capacity);
+    net.set_costs(E::cost);
+    let (s, t) = (net.index[source.get()], net.index[sink.get()]);
+    let (value, cost) = if s == t {
+        let (_, cost) = cancel_negative_cycles(&mut net);
+        (E::W::zero(), cost)
+    } else {
+        successive_shortest_paths(&mut net, s, t)
+    };
+    MinCostFlow { flow: MaxFlow::new(net, s, value), cost }
+}
+
+/// The cheapest flow where each vertex takes in `demand(v)` more than it
+///  sends out (so supplies are negative demands)
+pub(crate) fn min_cost_flow<'a, V, E, F>(g: &'a DiGraph<V,E>, demand: F)
+    -> Result<MinCostFlow<'a, V, E, E::W>, FlowError<E::W>>
+    where V: NodeT, E: CostT, F: Fn(&V) -> E::W
+{
+    let mut net = Residual::new(g, E::capacity);
+    net.set_costs(E::cost);
+    // a super-source feeding every supply and a super-sink draining every demand
+    let (s, t) = (net.add_vertex(), net.add_vertex());
+    let (mut supply, mut required) = (E::W::zero(), E::W::zero());
+    for i in 0 .. net.verts.len() {
+        let d = demand(net.verts[i].get());
+        if d < E::W::zero() {
+            net.add_arc(s, i, E::W::zero() - d, E::W::zero());
+            supply = supply + E::W::zero() - d;
+        } else if d > E::W::zero() {
+            net.add_arc(i, t, d, E::W::zero());
+            required = required + d;
+        }
+    }
+    let (routed, cost) = successive_shortest_paths(&mut net, s, t);
+    if routed < required || supply != required {
+        let required = cmp::max(supply, required);
+        return Err(FlowError::Infeasible { routed, required });
+    }
+    Ok(MinCostFlow { flow: MaxFlow::new(net, s, routed), cost })
+}
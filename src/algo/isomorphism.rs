@@ -0,0 +1,269 @@
+
+use std::collections::HashMap;
+
+use Graph;
+use dir::DirT;
+use edge::EdgeT;
+use vertex::{NodeT, Vertex};
+
+const NIL: usize = usize::MAX;
+
+/// Equivalence for when anything goes
+pub(crate) fn any<T>(_: &T, _: &T) -> bool {
+    true
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  VF2 state
+///////////////////////////////////////////////////////////////////////////////
+
+/// One of the two graphs being matched, with its vertices numbered
+struct Side<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>> {
+    verts: Vec<&'a Vertex<V,E,D>>,
+    // edges to and from each vertex, grouped by the vertex at the other end;
+    //  the same sets for undirected graphs
+    succ: Vec<HashMap<usize, Vec<&'a E>>>,
+    pred: Vec<HashMap<usize, Vec<&'a E>>>,
+    // the vertex each is mapped to on the other side
+    core: Vec<usize>,
+    // the depth at which each vertex joined the out- or in-terminal set (or 0):
+    //  unmapped vertices adjacent from or to a mapped one
+    t_out: Vec<usize>,
+    t_in: Vec<usize>,
+}
+
+impl<'a, V: NodeT, E: EdgeT, D: DirT<V,E>> Side<'a,V,E,D> {
+    fn new(g: &'a Graph<V,E,D>) -> Self {
+        let verts: Vec<_> = g.vertices().collect();
+        let n = verts.len();
+        let index: HashMap<&V, usize> = verts.iter().enumerate().map(|(i, v)| (v.get(), i)).collect();
+        let mut succ = vec![HashMap::new(); n];
+        let mut pred = vec![HashMap::new(); n];
+        for (i, &v) in verts.iter().enumerate() {
+            for (r, e) in g.get_reachable(v).with_edges() {
+                let j = index[r.get()];
+                succ[i].entry(j).or_insert_with(Vec::new).push(e);
+                pred[j].entry(i).or_insert_with(Vec::new).push(e);
+            }
+        }
+        Side { verts, succ, pred, core: vec![NIL; n], t_out: vec![0; n], t_in: vec![0; n] }
+    }
+    fn order(&self) -> usize {
+        self.verts.len()
+    }
+    fn size(&self) -> usize {
+        self.succ.iter().map(|s| s.values().map(|es| es.len()).sum::<usize>()).sum()
+    }
+    fn edges(&self, i: usize, j: usize) -> &[&'a E] {
+        self.succ[i].get(&j).map_or(&[], |es| &es[..])
+    }
+    fn add(&mut self, i: usize, other: usize, depth: usize) {
+        self.core[i] = other;
+        for t in [&mut self.t_out, &mut self.t_in].iter_mut() {
+            if t[i] == 0 { t[i] = depth; }
+        }
+        for &j in self.succ[i].keys() {
+            if self.t_out[j] == 0 { self.t_out[j] = depth; }
+        }
+        for &j in self.pred[i].keys() {
+            if self.t_in[j] == 0 { self.t_in[j] = depth; }
+        }
+    }
+    fn remove(&mut self, i: usize, depth: usize) {
+        self.core[i] = NIL;
+        for t in [&mut self.t_out, &mut self.t_in].iter_mut() {
+            for d in t.iter_mut().filter(|d| **d == depth) {
+                *d = 0;
+            }
+        }
+    }
+    /// Unmapped vertices in the given terminal set (or all of them if `None`)
+    fn candidates(&self, t: Option<&[usize]>) -> Vec<usize> {
+        (0 .. self.order())
+            .filter(|&i| self.core[i] == NIL && t.is_none_or(|t| t[i] != 0))
+            .collect()
+    }
+    /// Among `i`'s unmapped neighbors in one direction, how many are in the
+    ///  out-terminal set, the in-terminal set, and neither
+    fn look_ahead(&self, hood: &HashMap<usize, Vec<&'a E>>) -> (usize, usize, usize) {
+        hood.keys().filter(|&&j| self.core[j] == NIL).fold((0, 0, 0), |(o, i, new), &j| {
+            (o + (self.t_out[j] != 0) as usize,
+             i + (self.t_in[j] != 0) as usize,
+             new + (self.t_out[j] == 0 && self.t_in[j] == 0) as usize)
+        })
+    }
+}
+
+struct Frame {
+    pattern: usize,
+    hosts: Vec<usize>,
+    next: usize,
+    mapped: Option<usize>,
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  VF2
+///////////////////////////////////////////////////////////////////////////////
+
+/// Every way to map the vertices of a pattern graph onto a host graph so that
+///  edges map to edges, found by VF2. For subgraphs the match is induced: two
+///  mapped vertices are adjacent in the host exactly when they are in the pattern
+pub struct Isomorphisms<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>, FV, FE> {
+    host: Side<'a,V,E,D>,
+    pattern: Side<'a,V,E,D>,
+    node_eq: FV,
+    edge_eq: FE,
+    // whether the host has to be the same size as the pattern
+    whole: bool,
+    stack: Vec<Frame>,
+    started: bool,
+}
+
+impl<'a, V, E, D, FV, FE> Isomorphisms<'a,V,E,D,FV,FE>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>, FV: Fn(&V, &V) -> bool, FE: Fn(&E, &E) -> bool
+{
+    pub(crate) fn new(host: &'a Graph<V,E,D>,
+                      pattern: &'a Graph<V,E,D>,
+                      whole: bool,
+                      node_eq: FV,
+                      edge_eq: FE)
+        -> Self
+    {
+        Isomorphisms {
+            host: Side::new(host),
+            pattern: Side::new(pattern),
+            node_eq, edge_eq, whole,
+            stack: vec![],
+            started: false,
+        }
+    }
+
+    /// The next pattern vertex to map and the host vertices it could map to:
+    ///  one next to the mapping if there is one, which then has to map next to
+    ///  it in the same direction
+    fn frame(&self) -> Frame {
+        let (h, p) = (&self.host, &self.pattern);
+        let (p_out, p_in) = (p.candidates(Some(&p.t_out[..])), p.candidates(Some(&p.t_in[..])));
+        let (patterns, hosts) = if p_out.is_empty() == false {
+            (p_out, h.candidates(Some(&h.t_out[..])))
+        } else if p_in.is_empty() == false {
+            (p_in, h.candidates(Some(&h.t_in[..])))
+        } else {
+            (p.candidates(None), h.candidates(None))
+        };
+        match patterns.first() {
+            Some(&pattern) => Frame { pattern, hosts, next: 0, mapped: None },
+            None => Frame { pattern: NIL, hosts: vec![], next: 0, mapped: None },
+        }
+    }
+
+    /// Whether edges between `(h, h2)` can stand for those between `(p, p2)`,
+    ///  each parallel edge of the pattern paired with its own of the host's
+    fn same_edges(&self, hs: &[&E], ps: &[&E]) -> bool {
+        if hs.len() != ps.len() { return false }
+        let mut owner = vec![NIL; hs.len()];
+        (0 .. ps.len()).all(|p| self.claim(p, hs, ps, &mut owner, &mut vec![false; hs.len()]))
+    }
+
+    /// Find pattern edge `p` a host edge, moving others along to make room
+    ///  (an augmenting path, as in bipartite matching)
+    fn claim(&self, p: usize, hs: &[&E], ps: &[&E], owner: &mut [usize], seen: &mut [bool]) -> bool {
+        for h in 0 .. hs.len() {
+            if seen[h] || (self.edge_eq)(hs[h], ps[p]) == false { continue }
+            seen[h] = true;
+            if owner[h] == NIL || self.claim(owner[h], hs, ps, owner, seen) {
+                owner[h] = p;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Whether mapping pattern vertex `p` to host vertex `h` keeps the mapping
+    ///  consistent and leaves enough room to finish it
+    fn feasible(&self, p: usize, h: usize) -> bool {
+        let (host, pat) = (&self.host, &self.pattern);
+        if (self.node_eq)(host.verts[h].get(), pat.verts[p].get()) == false { return false }
+        if self.same_edges(host.edges(h, h), pat.edges(p, p)) == false { return false }
+        // edges to and from already-mapped vertices have to agree both ways
+        for (h_hood, p_hood, forward) in [(&host.succ[h], &pat.succ[p], true),
+                                          (&host.pred[h], &pat.pred[p], false)].iter() {
+            for &h2 in h_hood.keys().filter(|&&h2| h2 != h && host.core[h2] != NIL) {
+                if p_hood.contains_key(&host.core[h2]) == false { return false }
+            }
+            for (&p2, p_edges) in p_hood.iter().filter(|&(&p2, _)| p2 != p && pat.core[p2] != NIL) {
+                let h2 = pat.core[p2];
+                let h_edges = if *forward { host.edges(h, h2) } else { host.edges(h2, h) };
+                if self.same_edges(h_edges, p_edges) == false { return false }
+            }
+        }
+        // the host needs at least as many unmapped neighbors of each kind
+        for (h_hood, p_hood) in [(&host.succ[h], &pat.succ[p]), (&host.pred[h], &pat.pred[p])].iter() {
+            let (ho, hi, hn) = host.look_ahead(h_hood);
+            let (po, pi, pn) = pat.look_ahead(p_hood);
+            let room = if self.whole {
+                (ho, hi, hn) == (po, pi, pn)
+            } else {
+                ho >= po && hi >= pi && hn >= pn
+            };
+            if room == false { return false }
+        }
+        true
+    }
+
+    fn mapping(&self) -> HashMap<&'a V, &'a V> {
+        (0 .. self.pattern.order())
+            .map(|p| (self.pattern.verts[p].get(), self.host.verts[self.pattern.core[p]].get()))
+            .collect()
+    }
+}
+
+impl<'a, V, E, D, FV, FE> Iterator for Isomorphisms<'a,V,E,D,FV,FE>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>, FV: Fn(&V, &V) -> bool, FE: Fn(&E, &E) -> bool
+{
+    type Item = HashMap<&'a V, &'a V>;
+    fn next(&mut self) -> Option<HashMap<&'a V, &'a V>> {
+        if self.started == false {
+            self.started = true;
+            let (h, p) = (&self.host, &self.pattern);
+            let fits = if self.whole {
+                h.order() == p.order() && h.size() == p.size()
+            } else {
+                h.order() >= p.order() && h.size() >= p.size()
+            };
+            if fits == false { return None }
+            if p.order() == 0 { return Some(HashMap::new()) }
+            let frame = self.frame();
+            self.stack.push(frame);
+        }
+        loop {
+            let depth = self.stack.len();
+            let (p, h) = {
+                let frame = self.stack.last_mut()?;
+                if let Some(h) = frame.mapped.take() {
+                    let p = frame.pattern;
+                    self.host.remove(h, depth);
+                    self.pattern.remove(p, depth);
+                }
+                (frame.pattern, frame.hosts.get(frame.next).cloned())
+            };
+            let h = match h {
+                Some(h) => h,
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+            self.stack[depth - 1].next += 1;
+            if self.feasible(p, h) == false { continue }
+            self.host.add(h, p, depth);
+            self.pattern.add(p, h, depth);
+            self.stack[depth - 1].mapped = Some(h);
+            if depth == self.pattern.order() {
+                return Some(self.mapping());
+            }
+            let frame = self.frame();
+            self.stack.push(frame);
+        }
+    }
+}
@@ -0,0 +1,689 @@
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::{cmp, iter, mem};
+
+use UndirectedGraph;
+use dir::Undir;
+use edge::{EdgeT, WeightT, NumT};
+use path::Cycle;
+use vertex::{NodeT, Vertex};
+use super::{edge_list, EdgeRef};
+
+type UVertex<V,E> = Vertex<V, E, Undir<V,E>>;
+type UEdgeRef<'a,V,E> = EdgeRef<'a, V, E, Undir<V,E>>;
+
+///////////////////////////////////////////////////////////////////////////////
+//  Matching
+///////////////////////////////////////////////////////////////////////////////
+
+/// A set of edges no two of which share an endpoint
+#[derive(Debug)]
+pub struct Matching<'a, V: 'a+NodeT, E: 'a+EdgeT> {
+    edges: Vec<UEdgeRef<'a,V,E>>,
+    mates: HashMap<&'a V, &'a UVertex<V,E>>,
+}
+
+impl<'a, V: NodeT, E: EdgeT> Matching<'a,V,E> {
+    pub(crate) fn new(edges: Vec<UEdgeRef<'a,V,E>>) -> Self {
+        let mut mates = HashMap::with_capacity(2 * edges.len());
+        for &(l, _, r) in &edges {
+            let (ml, mr) = (mates.insert(l.get(), r), mates.insert(r.get(), l));
+            debug_assert!(ml.is_none() && mr.is_none(), "matched edges share an endpoint");
+        }
+        Matching { edges, mates }
+    }
+    pub fn edges(&self) -> &[UEdgeRef<'a,V,E>] {
+        &self.edges
+    }
+    /// Number of matched edges
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+    /// The vertex `v` is matched with, if any
+    pub fn mate(&self, v: &V) -> Option<&'a UVertex<V,E>> {
+        self.mates.get(v).cloned()
+    }
+    pub fn is_matched(&self, v: &V) -> bool {
+        self.mates.contains_key(v)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Hopcroft-Karp
+///////////////////////////////////////////////////////////////////////////////
+
+/// Maximum matching between the two sides of a bipartite graph, along with a
+///  minimum vertex cover (the same size, by König's theorem)
+pub(crate) fn hopcroft_karp<'a, V, E>(g: &'a UndirectedGraph<V,E>)
+    -> Result<(Matching<'a,V,E>, Vec<&'a UVertex<V,E>>), Cycle<'a, V, E, Undir<V,E>>>
+    where V: NodeT, E: EdgeT
+{
+    let (left, _) = g.bipartition()?;
+    let left: HashSet<&V> = left.into_iter().map(|v| v.get()).collect();
+    Ok(hopcroft_karp_by(g, |v| left.contains(v)))
+}
+
+/// As `hopcroft_karp`, but edges between two vertices on the same side are ignored
+pub(crate) fn hopcroft_karp_by<'a, V, E, F>(g: &'a UndirectedGraph<V,E>, is_left: F)
+    -> (Matching<'a,V,E>, Vec<&'a UVertex<V,E>>)
+    where V: NodeT, E: EdgeT, F: Fn(&V) -> bool
+{
+    let (left, right): (Vec<_>, Vec<_>) = g.vertices().partition(|v| is_left(v.get()));
+    let r_index: HashMap<&V, usize> = right.iter().enumerate().map(|(i, v)| (v.get(), i)).collect();
+    let adj: Vec<Vec<(usize, &E)>> = left.iter().map(|&l| {
+        g.get_neighbors(l).with_edges()
+            .filter_map(|(r, e)| r_index.get(r.get()).map(|&j| (j, e)))
+            .collect()
+    }).collect();
+    let mut mate_l: Vec<Option<(usize, &E)>> = vec![None; left.len()];
+    let mut mate_r: Vec<Option<usize>> = vec![None; right.len()];
+
+    loop {
+        // layer the left side by alternating distance from a free left vertex
+        let mut dist: Vec<Option<usize>> = vec![None; left.len()];
+        let mut queue: VecDeque<usize> = (0 .. left.len()).filter(|&i| mate_l[i].is_none()).collect();
+        for &i in &queue { dist[i] = Some(0); }
+        let mut shortest: Option<usize> = None;
+        while let Some(i) = queue.pop_front() {
+            let di = dist[i].unwrap();
+            if shortest.is_some_and(|s| di >= s) { continue }
+            for &(j, _) in &adj[i] {
+                match mate_r[j] {
+                    None => if shortest.is_none() { shortest = Some(di + 1) },
+                    Some(k) if dist[k].is_none() => {
+                        dist[k] = Some(di + 1);
+                        queue.push_back(k);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        let shortest = match shortest {
+            Some(s) => s,
+            None => break,
+        };
+
+        // vertex-disjoint shortest augmenting paths, by iterative depth-first search
+        let mut next = vec![0; left.len()];
+        for root in 0 .. left.len() {
+            if mate_l[root].is_some() { continue }
+            let mut stack = vec![root];
+            while let Some(&i) = stack.last() {
+                let di = dist[i];
+                if next[i] == adj[i].len() {
+                    dist[i] = None;
+                    stack.pop();
+                    if let Some(&p) = stack.last() { next[p] += 1; }
+                    continue;
+                }
+                let (j, _) = adj[i][next[i]];
+                match mate_r[j] {
+                    None if di.map(|d| d + 1) == Some(shortest) => {
+                        // flip every edge along the path
+                        for &k in stack.iter().rev() {
+                            let (j, e) = adj[k][next[k]];
+                            mate_l[k] = Some((j, e));
+                            mate_r[j] = Some(k);
+                        }
+                        break;
+                    }
+                    Some(k) if dist[k].is_some() && dist[k] == di.map(|d| d + 1) => stack.push(k),
+                    _ => next[i] += 1,
+                }
+            }
+        }
+    }
+
+    // König: vertices reachable from free left vertices by alternating paths
+    let mut seen_l = vec![false; left.len()];
+    let mut seen_r = vec![false; right.len()];
+    let mut queue: VecDeque<usize> = (0 .. left.len()).filter(|&i| mate_l[i].is_none()).collect();
+    for &i in &queue { seen_l[i] = true; }
+    while let Some(i) = queue.pop_front() {
+        for &(j, _) in &adj[i] {
+            if seen_r[j] || mate_l[i].map(|m| m.0) == Some(j) { continue }
+            seen_r[j] = true;
+            if let Some(k) = mate_r[j] {
+                if seen_l[k] == false {
+                    seen_l[k] = true;
+                    queue.push_back(k);
+                }
+            }
+        }
+    }
+    let cover = (0 .. left.len()).filter(|&i| seen_l[i] == false).map(|i| left[i])
+        .chain((0 .. right.len()).filter(|&j| seen_r[j]).map(|j| right[j]))
+        .collect();
+    let matched = mate_l.iter().enumerate()
+        .filter_map(|(i, m)| m.map(|(j, e)| (left[i], e, right[j])))
+        .collect();
+    (Matching::new(matched), cover)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Edmonds' blossom algorithm
+///////////////////////////////////////////////////////////////////////////////
+
+/// Heaviest matching of any undirected graph; edges that aren't positive 
+///  are never matched. O(n³)
+pub(crate) fn max_weight_matching<V, E>(g: &UndirectedGraph<V,E>) -> Matching<'_,V,E>
+    where V: NodeT, E: WeightT
+{
+    blossom(g, |e| e.weight())
+}
+
+/// Largest matching of any undirected graph, bipartite or not
+pub(crate) fn max_cardinality_matching<V, E>(g: &UndirectedGraph<V,E>) -> Matching<'_,V,E>
+    where V: NodeT, E: EdgeT
+{
+    // every maximum-weight matching is maximum-cardinality when weights are equal
+    blossom(g, |_| 1usize)
+}
+
+fn blossom<'a, V, E, W, F>(g: &'a UndirectedGraph<V,E>, weight: F) -> Matching<'a,V,E>
+    where V: NodeT, E: EdgeT, W: NumT, F: Fn(&E) -> W
+{
+    let verts: Vec<_> = g.vertices().collect();
+    let index: HashMap<&V, usize> = verts.iter().enumerate().map(|(i, v)| (v.get(), i)).collect();
+    // only the heaviest of a set of parallel edges could ever be matched
+    let mut pairs: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut edges: Vec<(usize, usize, i128)> = vec![];
+    let mut refs: Vec<&E> = vec![];
+    for (l, e, r) in edge_list(g) {
+        let (i, j, w) = (index[l.get()], index[r.get()], weight(e).to_i128());
+        if i == j || w <= 0 { continue }
+        match pairs.get(&(cmp::min(i, j), cmp::max(i, j))) {
+            Some(&k) if edges[k].2 >= w => {}
+            Some(&k) => { edges[k] = (i, j, w); refs[k] = e; }
+            None => {
+                pairs.insert((cmp::min(i, j), cmp::max(i, j)), edges.len());
+                edges.push((i, j, w));
+                refs.push(e);
+            }
+        }
+    }
+    let mate = Blossom::new(verts.len(), edges.clone()).solve();
+    let matched = edges.iter().enumerate()
+        .filter(|&(k, &(i, _, _))| mate[i] != NIL && mate[i] / 2 == k)
+        .map(|(k, &(i, j, _))| (verts[i], refs[k], verts[j]))
+        .collect();
+    Matching::new(matched)
+}
+
+/// Each of `0..n`'s partner in a heaviest matching of `edges`, given as
+///  `(i, j, weight)`
+pub(crate) fn max_weight_mates(n: usize, edges: Vec<(usize, usize, i128)>) -> Vec<Option<usize>> {
+    let blossom = Blossom::new(n, edges);
+    let endpoint = blossom.endpoint.clone();
+    blossom.solve().into_iter().map(|p| if p == NIL { None } else { Some(endpoint[p]) }).collect()
+}
+
+// After Van Rantwijk's `mwmatching.py`, which follows Galil (1986).
+//  Vertices are `0..n` and blossoms `n..2n`. Edge `k` has endpoints `2k` 
+//  and `2k+1`, so `p ^ 1` is the far end of endpoint `p`. Vertex duals are
+//  doubled (an edge's slack is `u + v - 2w`) so integer weights keep them integral.
+//  Weights are widened to `i128` first, so even `u64` weights can't overflow that.
+
+const NIL: usize = usize::MAX;
+const FREE: u8 = 0;
+const S: u8 = 1;
+const T: u8 = 2;
+const BREADCRUMB: u8 = 5;
+
+enum Delta {
+    Dual,
+    Edge(usize),
+    Expand(usize),
+}
+
+/// Python-style index into a blossom's cyclic child list
+fn wrap(j: isize, len: isize) -> usize {
+    j.rem_euclid(len) as usize
+}
+
+struct Blossom {
+    n: usize,
+    edges: Vec<(usize, usize, i128)>,
+    endpoint: Vec<usize>,
+    // the far endpoint of every edge touching each vertex
+    neighbend: Vec<Vec<usize>>,
+    // the endpoint each vertex is matched to
+    mate: Vec<usize>,
+    label: Vec<u8>,
+    // the endpoint a vertex or blossom got its label through
+    labelend: Vec<usize>,
+    inblossom: Vec<usize>,
+    parent: Vec<usize>,
+    childs: Vec<Vec<usize>>,
+    base: Vec<usize>,
+    endps: Vec<Vec<usize>>,
+    bestedge: Vec<usize>,
+    bestedges: Vec<Option<Vec<usize>>>,
+    unused: Vec<usize>,
+    dual: Vec<i128>,
+    allowed: Vec<bool>,
+    queue: Vec<usize>,
+}
+
+impl Blossom {
+    fn new(n: usize, edges: Vec<(usize, usize, i128)>) -> Self {
+        let max = edges.iter().map(|e| e.2).fold(0, cmp::max);
+        let endpoint = (0 .. 2 * edges.len())
+            .map(|p| if p % 2 == 0 { edges[p / 2].0 } else { edges[p / 2].1 })
+            .collect();
+        let mut neighbend = vec![vec![]; n];
+        for (k, &(i, j, _)) in edges.iter().enumerate() {
+            neighbend[i].push(2 * k + 1);
+            neighbend[j].push(2 * k);
+        }
+        Blossom {
+            n, endpoint, neighbend,
+            mate: vec![NIL; n],
+            label: vec![FREE; 2 * n],
+            labelend: vec![NIL; 2 * n],
+            inblossom: (0..n).collect(),
+            parent: vec![NIL; 2 * n],
+            childs: vec![vec![]; 2 * n],
+            base: (0..n).chain(iter::repeat_n(NIL, n)).collect(),
+            endps: vec![vec![]; 2 * n],
+            bestedge: vec![NIL; 2 * n],
+            bestedges: vec![None; 2 * n],
+            unused: (n .. 2 * n).collect(),
+            dual: iter::repeat_n(max, n).chain(iter::repeat_n(0, n)).collect(),
+            allowed: vec![false; edges.len()],
+            queue: vec![],
+            edges,
+        }
+    }
+
+    fn slack(&self, k: usize) -> i128 {
+        let (i, j, w) = self.edges[k];
+        self.dual[i] + self.dual[j] - w - w
+    }
+
+    fn leaves(&self, b: usize) -> Vec<usize> {
+        let mut leaves = vec![];
+        let mut stack = vec![b];
+        while let Some(t) = stack.pop() {
+            if t < self.n {
+                leaves.push(t);
+            } else {
+                stack.extend(self.childs[t].iter().rev());
+            }
+        }
+        leaves
+    }
+
+    /// Label the blossom containing `w` through endpoint `p`; a T-blossom's 
+    ///  mate becomes an S-blossom
+    fn assign_label(&mut self, w: usize, t: u8, p: usize) {
+        let b = self.inblossom[w];
+        debug_assert!(self.label[w] == FREE && self.label[b] == FREE);
+        self.label[w] = t;
+        self.label[b] = t;
+        self.labelend[w] = p;
+        self.labelend[b] = p;
+        self.bestedge[w] = NIL;
+        self.bestedge[b] = NIL;
+        if t == S {
+            let leaves = self.leaves(b);
+            self.queue.extend(leaves);
+        } else {
+            let m = self.mate[self.base[b]];
+            self.assign_label(self.endpoint[m], S, m ^ 1);
+        }
+    }
+
+    /// Walk back from two S-vertices towards their roots; the base of a new
+    ///  blossom if the walks meet, or `NIL` if they reach separate roots
+    fn scan(&mut self, mut v: usize, mut w: usize) -> usize {
+        let mut path = vec![];
+        let mut base = NIL;
+        while v != NIL {
+            let b = self.inblossom[v];
+            if self.label[b] & 4 != 0 {
+                base = self.base[b];
+                break;
+            }
+            path.push(b);
+            self.label[b] = BREADCRUMB;
+            v = match self.labelend[b] {
+                NIL => NIL,
+                p => self.endpoint[self.labelend[self.inblossom[self.endpoint[p]]]],
+            };
+            if w != NIL {
+                mem::swap(&mut v, &mut w);
+            }
+        }
+        for b in path {
+            self.label[b] = S;
+        }
+        base
+    }
+
+    /// Contract the odd cycle through edge `k` into a new S-blossom
+    fn add_blossom(&mut self, base: usize, k: usize) {
+        let (v, w, _) = self.edges[k];
+        let bb = self.inblossom[base];
+        let (mut bv, mut bw) = (self.inblossom[v], self.inblossom[w]);
+        let b = self.unused.pop().expect("more blossoms than vertices");
+        self.base[b] = base;
+        self.parent[b] = NIL;
+        self.parent[bb] = b;
+        let (mut path, mut endps) = (vec![], vec![]);
+        while bv != bb {
+            self.parent[bv] = b;
+            path.push(bv);
+            endps.push(self.labelend[bv]);
+            bv = self.inblossom[self.endpoint[self.labelend[bv]]];
+        }
+        path.push(bb);
+        path.reverse();
+        endps.reverse();
+        endps.push(2 * k);
+        while bw != bb {
+            self.parent[bw] = b;
+            path.push(bw);
+            endps.push(self.labelend[bw] ^ 1);
+            bw = self.inblossom[self.endpoint[self.labelend[bw]]];
+        }
+        self.label[b] = S;
+        self.labelend[b] = self.labelend[bb];
+        self.dual[b] = 0;
+        self.childs[b] = path.clone();
+        self.endps[b] = endps;
+        for v in self.leaves(b) {
+            if self.label[self.inblossom[v]] == T {
+                self.queue.push(v);
+            }
+            self.inblossom[v] = b;
+        }
+
+        // the least-slack edge from the new blossom to each other S-blossom
+        let mut bestedgeto = vec![NIL; 2 * self.n];
+        for bv in path {
+            let edges: Vec<usize> = match self.bestedges[bv].take() {
+                Some(list) => list,
+                None => self.leaves(bv).into_iter()
+                    .flat_map(|v| self.neighbend[v].iter().map(|p| p / 2))
+                    .collect(),
+            };
+            for k in edges {
+                let (i, j, _) = self.edges[k];
+                let j = if self.inblossom[j] == b { i } else { j };
+                let bj = self.inblossom[j];
+                if bj != b && self.label[bj] == S
+                    && (bestedgeto[bj] == NIL || self.slack(k) < self.slack(bestedgeto[bj]))
+                {
+                    bestedgeto[bj] = k;
+                }
+            }
+            self.bestedge[bv] = NIL;
+        }
+        let best: Vec<usize> = bestedgeto.into_iter().filter(|&k| k != NIL).collect();
+        self.bestedge[b] = best.iter().cloned().min_by_key(|&k| self.slack(k)).unwrap_or(NIL);
+        self.bestedges[b] = Some(best);
+    }
+
+    /// Dissolve blossom `b`; mid-stage, relabel the children of a T-blossom
+    ///  so the alternating tree stays intact
+    fn expand(&mut self, b: usize, endstage: bool) {
+        for s in self.childs[b].clone() {
+            self.parent[s] = NIL;
+            if s < self.n {
+                self.inblossom[s] = s;
+            } else if endstage && self.dual[s] == 0 {
+                self.expand(s, endstage);
+            } else {
+                for v in self.leaves(s) {
+                    self.inblossom[v] = s;
+                }
+            }
+        }
+        if endstage == false && self.label[b] == T {
+            let len = self.childs[b].len() as isize;
+            let entry = self.inblossom[self.endpoint[self.labelend[b] ^ 1]];
+            let mut j = self.childs[b].iter().position(|&c| c == entry).unwrap() as isize;
+            // walk the even-length way round from the entry child to the base
+            let (step, trick) = if j & 1 == 1 { j -= len; (1, 0) } else { (-1, 1) };
+            let mut p = self.labelend[b];
+            while j != 0 {
+                let q = self.endps[b][wrap(j - trick, len)] ^ trick as usize;
+                self.label[self.endpoint[p ^ 1]] = FREE;
+                self.label[self.endpoint[q ^ 1]] = FREE;
+                self.assign_label(self.endpoint[p ^ 1], T, p);
+                self.allowed[q / 2] = true;
+                j += step;
+                p = self.endps[b][wrap(j - trick, len)] ^ trick as usize;
+                self.allowed[p / 2] = true;
+                j += step;
+            }
+            let (bv, w) = (self.childs[b][0], self.endpoint[p ^ 1]);
+            self.label[w] = T;
+            self.label[bv] = T;
+            self.labelend[w] = p;
+            self.labelend[bv] = p;
+            self.bestedge[bv] = NIL;
+            j += step;
+            // the rest of the cycle is unlabeled unless reached from outside
+            while self.childs[b][wrap(j, len)] != entry {
+                let bv = self.childs[b][wrap(j, len)];
+                j += step;
+                if self.label[bv] == S { continue }
+                if let Some(v) = self.leaves(bv).into_iter().find(|&v| self.label[v] != FREE) {
+                    debug_assert!(self.label[v] == T && self.inblossom[v] == bv);
+                    self.label[v] = FREE;
+                    self.label[self.endpoint[self.mate[self.base[bv]]]] = FREE;
+                    let p = self.labelend[v];
+                    self.assign_label(v, T, p);
+                }
+            }
+        }
+        self.label[b] = FREE;
+        self.labelend[b] = NIL;
+        self.childs[b].clear();
+        self.endps[b].clear();
+        self.base[b] = NIL;
+        self.bestedge[b] = NIL;
+        self.bestedges[b] = None;
+        self.unused.push(b);
+    }
+
+    /// Flip the matching inside `b` so that leaf `v` becomes its base
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let mut t = v;
+        while self.parent[t] != b {
+            t = self.parent[t];
+        }
+        if t >= self.n {
+            self.augment_blossom(t, v);
+        }
+        let len = self.childs[b].len() as isize;
+        let i = self.childs[b].iter().position(|&c| c == t).unwrap();
+        let mut j = i as isize;
+        let (step, trick) = if j & 1 == 1 { j -= len; (1, 0) } else { (-1, 1) };
+        while j != 0 {
+            j += step;
+            let t = self.childs[b][wrap(j, len)];
+            let p = self.endps[b][wrap(j - trick, len)] ^ trick as usize;
+            if t >= self.n {
+                let v = self.endpoint[p];
+                self.augment_blossom(t, v);
+            }
+            j += step;
+            let t = self.childs[b][wrap(j, len)];
+            if t >= self.n {
+                let v = self.endpoint[p ^ 1];
+                self.augment_blossom(t, v);
+            }
+            self.mate[self.endpoint[p]] = p ^ 1;
+            self.mate[self.endpoint[p ^ 1]] = p;
+        }
+        self.childs[b].rotate_left(i);
+        self.endps[b].rotate_left(i);
+        self.base[b] = self.base[self.childs[b][0]];
+        debug_assert_eq!(self.base[b], v);
+    }
+
+    /// Flip every edge on the augmenting path through edge `k`
+    fn augment_matching(&mut self, k: usize) {
+        let (v, w, _) = self.edges[k];
+        for &(s, p) in &[(v, 2 * k + 1), (w, 2 * k)] {
+            let (mut s, mut p) = (s, p);
+            loop {
+                let bs = self.inblossom[s];
+                if bs >= self.n {
+                    self.augment_blossom(bs, s);
+                }
+                self.mate[s] = p;
+                if self.labelend[bs] == NIL { break }
+                let bt = self.inblossom[self.endpoint[self.labelend[bs]]];
+                s = self.endpoint[self.labelend[bt]];
+                let j = self.endpoint[self.labelend[bt] ^ 1];
+                if bt >= self.n {
+                    self.augment_blossom(bt, j);
+                }
+                self.mate[j] = self.labelend[bt];
+                p = self.labelend[bt] ^ 1;
+            }
+        }
+    }
+
+    /// Each vertex's matched endpoint, or `NIL`
+    fn solve(mut self) -> Vec<usize> {
+        let n = self.n;
+        let two = 2;
+        // every stage either augments the matching or proves it's maximum
+        for _ in 0..n {
+            self.label.fill(FREE);
+            self.bestedge.fill(NIL);
+            for b in n .. 2 * n {
+                self.bestedges[b] = None;
+            }
+            self.allowed.fill(false);
+            self.queue.clear();
+            for v in 0..n {
+                if self.mate[v] == NIL && self.label[self.inblossom[v]] == FREE {
+                    self.assign_label(v, S, NIL);
+                }
+            }
+
+            let mut augmented = false;
+            loop {
+                'scan: while let Some(v) = self.queue.pop() {
+                    for i in 0 .. self.neighbend[v].len() {
+                        let p = self.neighbend[v][i];
+                        let (k, w) = (p / 2, self.endpoint[p]);
+                        if self.inblossom[v] == self.inblossom[w] { continue }
+                        let kslack = self.slack(k);
+                        if kslack <= 0 {
+                            self.allowed[k] = true;
+                        }
+                        let bw = self.inblossom[w];
+                        if self.allowed[k] {
+                            if self.label[bw] == FREE {
+                                self.assign_label(w, T, p ^ 1);
+                            } else if self.label[bw] == S {
+                                match self.scan(v, w) {
+                                    NIL => {
+                                        self.augment_matching(k);
+                                        augmented = true;
+                                        break 'scan;
+                                    }
+                                    base => self.add_blossom(base, k),
+                                }
+                            } else if self.label[w] == FREE {
+                                // a vertex inside a T-blossom is reachable by an even path
+                                self.label[w] = T;
+                                self.labelend[w] = p ^ 1;
+                            }
+                        } else if self.label[bw] == S {
+                            let b = self.inblossom[v];
+                            if self.bestedge[b] == NIL || kslack < self.slack(self.bestedge[b]) {
+                                self.bestedge[b] = k;
+                            }
+                        } else if self.label[w] == FREE
+                            && (self.bestedge[w] == NIL || kslack < self.slack(self.bestedge[w]))
+                        {
+                            self.bestedge[w] = k;
+                        }
+                    }
+                }
+                if augmented { break }
+
+                // no tight edge left to follow: adjust the duals as far as possible
+                let mut delta = self.dual[..n].iter().cloned().min().unwrap();
+                let mut kind = Delta::Dual;
+                for v in 0..n {
+                    if self.label[self.inblossom[v]] == FREE && self.bestedge[v] != NIL {
+                        let d = self.slack(self.bestedge[v]);
+                        if d < delta {
+                            delta = d;
+                            kind = Delta::Edge(self.bestedge[v]);
+                        }
+                    }
+                }
+                for b in 0 .. 2 * n {
+                    if self.parent[b] == NIL && self.label[b] == S && self.bestedge[b] != NIL {
+                        let slack = self.slack(self.bestedge[b]);
+                        debug_assert!(slack == slack / two * two, "odd slack between S-blossoms");
+                        if slack / two < delta {
+                            delta = slack / two;
+                            kind = Delta::Edge(self.bestedge[b]);
+                        }
+                    }
+                }
+                for b in n .. 2 * n {
+                    if self.base[b] != NIL && self.parent[b] == NIL && self.label[b] == T
+                        && self.dual[b] < delta
+                    {
+                        delta = self.dual[b];
+                        kind = Delta::Expand(b);
+                    }
+                }
+
+                for v in 0..n {
+                    match self.label[self.inblossom[v]] {
+                        S => self.dual[v] -= delta,
+                        T => self.dual[v] += delta,
+                        _ => {}
+                    }
+                }
+                for b in n .. 2 * n {
+                    if self.base[b] != NIL && self.parent[b] == NIL {
+                        match self.label[b] {
+                            S => self.dual[b] += delta,
+                            T => self.dual[b] -= delta,
+                            _ => {}
+                        }
+                    }
+                }
+
+                match kind {
+                    // some S-vertex's dual hit zero, so no augmenting path is worth taking
+                    Delta::Dual => break,
+                    Delta::Edge(k) => {
+                        self.allowed[k] = true;
+                        let (i, j, _) = self.edges[k];
+                        let i = if self.label[self.inblossom[i]] == FREE { j } else { i };
+                        self.queue.push(i);
+                    }
+                    Delta::Expand(b) => self.expand(b, false),
+                }
+            }
+            if augmented == false { break }
+
+            for b in n .. 2 * n {
+                if self.parent[b] == NIL && self.base[b] != NIL && self.label[b] == S
+                    && self.dual[b] == 0
+                {
+                    self.expand(b, true);
+                }
+            }
+        }
+        self.mate
+    }
+}
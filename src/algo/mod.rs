@@ -0,0 +1,144 @@
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use {Graph, UndirectedGraph};
+use dir::{DirT, Undir};
+use edge::EdgeT;
+use vertex::{NodeT, Vertex};
+
+pub mod shortest_path;
+pub use self::shortest_path::{ShortestPaths, NegativeCycle};
+
+pub mod all_pairs;
+pub use self::all_pairs::AllPairs;
+
+pub mod spanning_tree;
+
+pub mod connectivity;
+
+pub mod bipartite;
+
+pub mod flow;
+pub use self::flow::{MaxFlow, MinCostFlow, FlowError};
+
+pub mod matching;
+pub use self::matching::Matching;
+
+pub mod assignment;
+pub use self::assignment::{Assignment, AssignmentError};
+
+pub mod euler;
+
+pub mod tour;
+
+pub mod isomorphism;
+pub use self::isomorphism::Isomorphisms;
+
+pub mod clique;
+pub use self::clique::MaximalCliques;
+
+pub mod coloring;
+pub use self::coloring::ColorOrder;
+
+pub mod pagerank;
+pub use self::pagerank::{PageRankConfig, Dangling};
+
+pub mod centrality;
+
+pub mod community;
+pub use self::community::Communities;
+
+pub mod triangles;
+
+mod union_find;
+
+///////////////////////////////////////////////////////////////////////////////
+//  Helpers
+///////////////////////////////////////////////////////////////////////////////
+
+/// Priority queue entry ordered so a `BinaryHeap` pops the smallest key first
+pub(crate) struct MinFirst<K: Ord, T>(pub K, pub T);
+
+impl<K: Ord, T> PartialEq for MinFirst<K,T> {
+    fn eq(&self, other: &Self) -> bool { self.0 == other.0 }
+}
+impl<K: Ord, T> Eq for MinFirst<K,T> {}
+impl<K: Ord, T> PartialOrd for MinFirst<K,T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+impl<K: Ord, T> Ord for MinFirst<K,T> {
+    fn cmp(&self, other: &Self) -> Ordering { other.0.cmp(&self.0) }
+}
+
+/// An edge along with its endpoints, as `(src, edge, dst)`
+pub type EdgeRef<'a, V, E, D> = (&'a Vertex<V,E,D>, &'a E, &'a Vertex<V,E,D>);
+
+/// Every edge once; undirected edges are stored in both endpoints'
+///  neighborhoods so they're deduplicated by address
+pub(crate) fn edge_list<V, E, D>(g: &Graph<V,E,D>) -> Vec<EdgeRef<'_,V,E,D>>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>
+{
+    let mut seen: HashSet<*const E> = HashSet::with_capacity(g.size());
+    let mut edges = Vec::with_capacity(g.size());
+    for src in g.vertices() {
+        for (dst, edge) in g.get_reachable(src).with_edges() {
+            if seen.insert(edge) {
+                edges.push((src, edge, dst));
+            }
+        }
+    }
+    edges
+}
+
+/// Vertices numbered with the neighbors of each, so that adjacency is a
+///  hash lookup rather than a scan of a vertex's edges
+pub(crate) struct Neighborhoods<'a, V: 'a+NodeT, E: 'a+EdgeT> {
+    pub(crate) verts: Vec<&'a Vertex<V, E, Undir<V,E>>>,
+    pub(crate) hoods: Vec<HashSet<usize>>,
+}
+
+impl<'a, V: NodeT, E: EdgeT> Neighborhoods<'a,V,E> {
+    /// Self-loops are left out
+    pub(crate) fn new(g: &'a UndirectedGraph<V,E>) -> Self {
+        let verts: Vec<_> = g.vertices().collect();
+        let index: HashMap<&V, usize> = verts.iter().enumerate().map(|(i, v)| (v.get(), i)).collect();
+        let hoods = verts.iter().enumerate().map(|(i, &v)| {
+            g.get_neighbors(v).map(|n| index[n.get()]).filter(|&j| j != i).collect()
+        }).collect();
+        Neighborhoods { verts, hoods }
+    }
+
+    /// Vertices in the order they'd be removed by repeatedly taking one of
+    ///  least remaining degree, so each has few neighbors later in the order
+    pub(crate) fn degeneracy_order(&self) -> Vec<usize> {
+        let n = self.verts.len();
+        let mut degree: Vec<usize> = self.hoods.iter().map(|h| h.len()).collect();
+        let mut buckets = vec![vec![]; degree.iter().max().map_or(0, |&d| d + 1)];
+        for (i, &d) in degree.iter().enumerate() {
+            buckets[d].push(i);
+        }
+        let mut removed = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+        let mut d = 0;
+        while order.len() < n {
+            // entries are left behind when a degree drops, so skip stale ones
+            let i = match buckets[d].pop() {
+                Some(i) if removed[i] == false && degree[i] == d => i,
+                Some(_) => continue,
+                None => { d += 1; continue }
+            };
+            removed[i] = true;
+            order.push(i);
+            for &j in &self.hoods[i] {
+                if removed[j] == false {
+                    degree[j] -= 1;
+                    buckets[degree[j]].push(j);
+                }
+            }
+            // removing `i` can only have lowered the least degree by one
+            d = d.saturating_sub(1);
+        }
+        order
+    }
+}
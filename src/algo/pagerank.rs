@@ -0,0 +1,100 @@
+
+use std::collections::HashMap;
+
+use DiGraph;
+use dir::Dir;
+use edge::EdgeT;
+use vertex::{NodeT, Vertex};
+
+/// Where a random surfer goes from a vertex with no way out
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dangling {
+    /// Jump the same way it would when teleporting: anywhere for plain
+    ///  PageRank, back to the seeds for personalized PageRank
+    Teleport,
+    /// Jump to any vertex, even when personalized
+    Uniform,
+    /// Stay put, as if the vertex linked to itself
+    Stay,
+}
+
+/// Settings for PageRank; `PageRankConfig::default()` gives the usual ones
+#[derive(Clone, Copy, Debug)]
+pub struct PageRankConfig {
+    /// Chance of following an edge rather than teleporting
+    pub damping: f64,
+    /// Stop once no rank changes by more than this in total
+    pub tolerance: f64,
+    /// Stop after this many rounds even if the ranks haven't settled
+    pub max_iterations: usize,
+    pub dangling: Dangling,
+}
+
+impl Default for PageRankConfig {
+    fn default() -> Self {
+        PageRankConfig { damping: 0.85, tolerance: 1e-9, max_iterations: 100, dangling: Dangling::Teleport }
+    }
+}
+
+/// Stationary distribution of a surfer who follows an edge out of its vertex
+///  with chance `damping` and otherwise teleports to one of `seeds` (or
+///  anywhere if there are none), by power iteration. Edges are chosen in
+///  proportion to `weight`, and never if it isn't positive. Ranks sum to one
+pub(crate) fn page_rank<'a, V, E, F>(g: &'a DiGraph<V,E>,
+                                     seeds: &[&Vertex<V, E, Dir<V,E>>],
+                                     weight: F,
+                                     config: &PageRankConfig)
+    -> HashMap<&'a V, f64>
+    where V: NodeT, E: EdgeT, F: Fn(&E) -> f64
+{
+    let verts: Vec<_> = g.vertices().collect();
+    let n = verts.len();
+    if n == 0 { return HashMap::new() }
+    let index: HashMap<&V, usize> = verts.iter().enumerate().map(|(i, v)| (v.get(), i)).collect();
+
+    // chance of each step out of a vertex; none at all if it's dangling
+    let steps: Vec<Vec<(usize, f64)>> = verts.iter().map(|&v| {
+        let out: Vec<_> = g.get_children(v).with_edges()
+            .map(|(c, e)| (index[c.get()], weight(e)))
+            .filter(|&(_, w)| w > 0.0)
+            .collect();
+        let total: f64 = out.iter().map(|&(_, w)| w).sum();
+        out.into_iter().map(|(j, w)| (j, w / total)).collect()
+    }).collect();
+    let uniform = vec![1.0 / n as f64; n];
+    let teleport = if seeds.is_empty() {
+        uniform.clone()
+    } else {
+        let mut t = vec![0.0; n];
+        for s in seeds {
+            t[index[s.get()]] += 1.0 / seeds.len() as f64;
+        }
+        t
+    };
+
+    let d = config.damping;
+    let mut rank = teleport.clone();
+    for _ in 0 .. config.max_iterations {
+        let mut next: Vec<f64> = teleport.iter().map(|t| (1.0 - d) * t).collect();
+        let mut lost = 0.0;
+        for (i, out) in steps.iter().enumerate() {
+            if out.is_empty() {
+                match config.dangling {
+                    Dangling::Stay => next[i] += d * rank[i],
+                    _ => lost += rank[i],
+                }
+            }
+            for &(j, p) in out {
+                next[j] += d * p * rank[i];
+            }
+        }
+        let spread = if config.dangling == Dangling::Uniform { &uniform } else { &teleport };
+        for (r, s) in next.iter_mut().zip(spread) {
+            *r += d * lost * s;
+        }
+        let change: f64 = rank.iter().zip(&next).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if change <= config.tolerance { break }
+    }
+    verts.into_iter().zip(rank).map(|(v, r)| (v.get(), r)).collect()
+}
@@ -0,0 +1,338 @@
+
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+use Graph;
+use path::Path;
+use dir::DirT;
+use edge::{EdgeT, WeightT, NumT};
+use vertex::{NodeT, Vertex};
+use super::MinFirst;
+
+///////////////////////////////////////////////////////////////////////////////
+//  Single-source results
+///////////////////////////////////////////////////////////////////////////////
+
+/// Distances and predecessors of every vertex reachable from `source`
+#[derive(Debug)]
+pub struct ShortestPaths<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>, W> {
+    graph: &'a Graph<V,E,D>,
+    source: &'a Vertex<V,E,D>,
+    dist: HashMap<&'a V, W>,
+    pred: HashMap<&'a V, (&'a Vertex<V,E,D>, &'a E)>,
+}
+
+impl<'a, V: NodeT, E: EdgeT, D: DirT<V,E>, W: Copy> ShortestPaths<'a,V,E,D,W> {
+    pub(crate) fn new(graph: &'a Graph<V,E,D>,
+                      source: &'a Vertex<V,E,D>,
+                      dist: HashMap<&'a V, W>,
+                      pred: HashMap<&'a V, (&'a Vertex<V,E,D>, &'a E)>)
+        -> Self
+    {
+        ShortestPaths { graph, source, dist, pred }
+    }
+
+    pub fn source(&self) -> &'a Vertex<V,E,D> {
+        self.source
+    }
+    /// Length of the shortest path to `v`, or `None` if it's unreachable
+    pub fn distance(&self, v: &V) -> Option<W> {
+        self.dist.get(v).cloned()
+    }
+    pub fn distances(&self) -> &HashMap<&'a V, W> {
+        &self.dist
+    }
+    /// The vertex before `v` on its shortest path and the edge between them
+    pub fn predecessor(&self, v: &V) -> Option<(&'a Vertex<V,E,D>, &'a E)> {
+        self.pred.get(v).cloned()
+    }
+    pub fn predecessors(&self) -> &HashMap<&'a V, (&'a Vertex<V,E,D>, &'a E)> {
+        &self.pred
+    }
+    /// Walk the predecessors back from `v`, or `None` if it's unreachable
+    pub fn path_to(&self, v: &V) -> Option<Path<'a,V,E,D>> {
+        self.dist.get(v)?;
+        let end = self.graph.get_vertex(v)?;
+        let mut steps = vec![];
+        let mut cur: &V = end.get();
+        while let Some(&(prev, edge)) = self.pred.get(cur) {
+            steps.push((prev, edge));
+            cur = prev.get();
+            debug_assert!(steps.len() <= self.dist.len(), "cycle in predecessors");
+        }
+        Some(Path::from_reversed(end, steps))
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Dijkstra
+///////////////////////////////////////////////////////////////////////////////
+
+/// Weights must be non-negative
+pub(crate) fn dijkstra<'a, V, E, D>(g: &'a Graph<V,E,D>, start: &'a Vertex<V,E,D>)
+    -> ShortestPaths<'a, V, E, D, E::W>
+    where V: NodeT, E: WeightT, D: DirT<V,E>
+{
+    dijkstra_by(g, start, |_, e, _| e.weight())
+}
+
+/// Dijkstra where an edge's length may depend on its endpoints
+pub(crate) fn dijkstra_by<'a, V, E, D, W, C>(g: &'a Graph<V,E,D>, 
+                                             start: &'a Vertex<V,E,D>,
+                                             cost: C)
+    -> ShortestPaths<'a, V, E, D, W>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>, W: NumT, 
+          C: Fn(&Vertex<V,E,D>, &E, &Vertex<V,E,D>) -> W
+{
+    settle_by(g, start, cost).into_paths(g, start)
+}
+
+/// Every shortest path from one source at once: the vertices in the order
+///  they were settled (so by distance), every edge that ends a shortest path
+///  to each, and how many shortest paths reach each
+pub(crate) struct Settled<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>, W> {
+    pub order: Vec<&'a Vertex<V,E,D>>,
+    pub dist: HashMap<&'a V, W>,
+    pub preds: HashMap<&'a V, Vec<(&'a Vertex<V,E,D>, &'a E)>>,
+    pub count: HashMap<&'a V, f64>,
+}
+
+impl<'a, V: NodeT, E: EdgeT, D: DirT<V,E>, W: NumT> Settled<'a,V,E,D,W> {
+    fn new(g: &Graph<V,E,D>, start: &'a Vertex<V,E,D>) -> Self {
+        let mut settled = Settled {
+            order: Vec::with_capacity(g.order()),
+            dist: HashMap::with_capacity(g.order()),
+            preds: HashMap::with_capacity(g.order()),
+            count: HashMap::with_capacity(g.order()),
+        };
+        settled.dist.insert(start.get(), W::zero());
+        settled.preds.insert(start.get(), vec![]);
+        settled.count.insert(start.get(), 1.0);
+        settled
+    }
+
+    /// Note a path to `next` of length `alt` ending with `edge` from `cur`;
+    ///  true if it's shorter than any before
+    fn reach(&mut self, cur: &'a Vertex<V,E,D>, edge: &'a E, next: &'a Vertex<V,E,D>, alt: W) -> bool {
+        let through = self.count[cur.get()];
+        match self.dist.get(next.get()) {
+            Some(&old) if old < alt => false,
+            Some(&old) if old == alt => {
+                *self.count.get_mut(next.get()).unwrap() += through;
+                self.preds.get_mut(next.get()).unwrap().push((cur, edge));
+                false
+            }
+            _ => {
+                self.dist.insert(next.get(), alt);
+                self.count.insert(next.get(), through);
+                self.preds.insert(next.get(), vec![(cur, edge)]);
+                true
+            }
+        }
+    }
+
+    /// Just the first way found onto each shortest path
+    fn into_paths(self, g: &'a Graph<V,E,D>, start: &'a Vertex<V,E,D>) -> ShortestPaths<'a,V,E,D,W> {
+        let pred = self.preds.into_iter()
+            .filter_map(|(v, preds)| preds.first().map(|&p| (v, p)))
+            .collect();
+        ShortestPaths::new(g, start, self.dist, pred)
+    }
+}
+
+/// Dijkstra keeping every tied predecessor; weights must be non-negative,
+///  and positive for the path counts to be right
+pub(crate) fn settle_by<'a, V, E, D, W, C>(g: &'a Graph<V,E,D>, start: &'a Vertex<V,E,D>, cost: C)
+    -> Settled<'a, V, E, D, W>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>, W: NumT, 
+          C: Fn(&Vertex<V,E,D>, &E, &Vertex<V,E,D>) -> W
+{
+    let mut settled = Settled::new(g, start);
+    let mut done = HashSet::with_capacity(g.order());
+    let mut queue = BinaryHeap::new();
+    queue.push(MinFirst(W::zero(), start));
+
+    while let Some(MinFirst(d, cur)) = queue.pop() {
+        if done.insert(cur.get()) == false {
+            continue;
+        }
+        settled.order.push(cur);
+        for (next, edge) in g.get_reachable(cur).with_edges() {
+            if done.contains(next.get()) { continue }
+            let alt = d + cost(cur, edge, next);
+            if settled.reach(cur, edge, next, alt) {
+                queue.push(MinFirst(alt, next));
+            }
+        }
+    }
+    settled
+}
+
+/// As `settle_by` with every edge of length one, which a breadth-first
+///  search settles without the heap
+pub(crate) fn settle_breadth_first<'a, V, E, D>(g: &'a Graph<V,E,D>, start: &'a Vertex<V,E,D>)
+    -> Settled<'a, V, E, D, usize>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>
+{
+    let mut settled = Settled::new(g, start);
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+
+    while let Some(cur) = queue.pop_front() {
+        settled.order.push(cur);
+        let d = settled.dist[cur.get()];
+        for (next, edge) in g.get_reachable(cur).with_edges() {
+            if settled.reach(cur, edge, next, d + 1) {
+                queue.push_back(next);
+            }
+        }
+    }
+    settled
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Bellman-Ford
+///////////////////////////////////////////////////////////////////////////////
+
+/// A cycle whose edges sum to less than zero, so no shortest paths exist
+#[derive(Debug)]
+pub struct NegativeCycle<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>> {
+    cycle: Path<'a,V,E,D>,
+}
+
+impl<'a, V: NodeT, E: EdgeT, D: DirT<V,E>> NegativeCycle<'a,V,E,D> {
+    /// The cycle as a closed path (its first and last vertices are the same)
+    pub fn path(&self) -> &Path<'a,V,E,D> {
+        &self.cycle
+    }
+    pub fn into_path(self) -> Path<'a,V,E,D> {
+        self.cycle
+    }
+}
+
+/// Weights may be negative; fails if a negative cycle is reachable from `start`
+pub(crate) fn bellman_ford<'a, V, E, D>(g: &'a Graph<V,E,D>, start: &'a Vertex<V,E,D>)
+    -> Result<ShortestPaths<'a, V, E, D, E::W>, NegativeCycle<'a,V,E,D>>
+    where V: NodeT, E: WeightT, D: DirT<V,E>
+{
+    let mut dist = HashMap::with_capacity(g.order());
+    dist.insert(start.get(), E::W::zero());
+    let pred = relax_all(g, &mut dist)?;
+    Ok(ShortestPaths::new(g, start, dist, pred))
+}
+
+/// Distances from a virtual vertex with a zero-weight edge to every vertex
+///
+/// Used by Johnson's algorithm to make every edge weight non-negative
+pub(crate) fn potentials<'a, V, E, D>(g: &'a Graph<V,E,D>)
+    -> Result<HashMap<&'a V, E::W>, NegativeCycle<'a,V,E,D>>
+    where V: NodeT, E: WeightT, D: DirT<V,E>
+{
+    let mut dist = g.vertices().map(|v| (v.get(), E::W::zero())).collect();
+    relax_all(g, &mut dist)?;
+    Ok(dist)
+}
+
+/// Repeatedly relax every edge leaving a vertex in `dist`
+fn relax_all<'a, V, E, D>(g: &'a Graph<V,E,D>, dist: &mut HashMap<&'a V, E::W>)
+    -> Result<HashMap<&'a V, (&'a Vertex<V,E,D>, &'a E)>, NegativeCycle<'a,V,E,D>>
+    where V: NodeT, E: WeightT, D: DirT<V,E>
+{
+    let mut pred = HashMap::with_capacity(g.order());
+    // the n'th round only relaxes an edge if there's a negative cycle
+    for round in 0 .. g.order() {
+        let mut relaxed = None;
+        for cur in g.vertices() {
+            let d = match dist.get(cur.get()) {
+                Some(&d) => d,
+                None => continue,
+            };
+            for (next, edge) in g.get_reachable(cur).with_edges() {
+                let alt = d + edge.weight();
+                if dist.get(next.get()).is_none_or(|&old| alt < old) {
+                    dist.insert(next.get(), alt);
+                    pred.insert(next.get(), (cur, edge));
+                    relaxed = Some(next);
+                }
+            }
+        }
+        match relaxed {
+            None => break,
+            Some(v) if round + 1 == g.order() => {
+                return Err(NegativeCycle { cycle: trace_cycle(&pred, v, g.order()) })
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(pred)
+}
+
+/// Find the cycle that `from` leads back into by following predecessors
+fn trace_cycle<'a, V, E, D>(pred: &HashMap<&V, (&'a Vertex<V,E,D>, &'a E)>,
+                            from: &'a Vertex<V,E,D>,
+                            n: usize)
+    -> Path<'a,V,E,D>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>
+{
+    // `n` steps back is guaranteed to land on the cycle itself
+    let mut on_cycle = from;
+    for _ in 0 .. n {
+        on_cycle = pred[on_cycle.get()].0;
+    }
+    let mut steps = vec![];
+    let mut cur = on_cycle;
+    loop {
+        let (prev, edge) = pred[cur.get()];
+        steps.push((prev, edge));
+        cur = prev;
+        if cur.get() == on_cycle.get() { break }
+    }
+    Path::from_reversed(on_cycle, steps)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  A*
+///////////////////////////////////////////////////////////////////////////////
+
+/// `heuristic` must never overestimate the remaining cost to `goal`
+pub(crate) fn astar<'a, V, E, D, W, H, C>(g: &'a Graph<V,E,D>,
+                                          start: &'a Vertex<V,E,D>,
+                                          goal: &'a Vertex<V,E,D>,
+                                          heuristic: H,
+                                          cost: C)
+    -> Option<(Path<'a,V,E,D>, W)>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>, W: NumT, H: Fn(&V) -> W, C: Fn(&E) -> W
+{
+    let mut dist: HashMap<&V, W> = HashMap::new();
+    let mut pred: HashMap<&V, (&Vertex<V,E,D>, &E)> = HashMap::new();
+    let mut done = HashSet::new();
+    let mut queue = BinaryHeap::new();
+    dist.insert(start.get(), W::zero());
+    queue.push(MinFirst(heuristic(start.get()), start));
+
+    while let Some(MinFirst(_, cur)) = queue.pop() {
+        if cur.get() == goal.get() {
+            let mut steps = vec![];
+            let mut at = cur.get();
+            while let Some(&(prev, edge)) = pred.get(at) {
+                steps.push((prev, edge));
+                at = prev.get();
+            }
+            return Some((Path::from_reversed(cur, steps), dist[cur.get()]));
+        }
+        if done.insert(cur.get()) == false {
+            continue;
+        }
+        let d = dist[cur.get()];
+        for (next, edge) in g.get_reachable(cur).with_edges() {
+            let alt = d + cost(edge);
+            if dist.get(next.get()).is_none_or(|&old| alt < old) {
+                dist.insert(next.get(), alt);
+                pred.insert(next.get(), (cur, edge));
+                // an inconsistent heuristic can make a closed vertex worth revisiting
+                done.remove(next.get());
+                queue.push(MinFirst(alt + heuristic(next.get()), next));
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,102 @@
+
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use {Graph, UndirectedGraph};
+use dir::Undir;
+use edge::EdgeT;
+use vertex::NodeT;
+use super::{edge_list, EdgeRef, MinFirst};
+use super::union_find::UnionFind;
+
+type Span<'a,V,E> = Vec<EdgeRef<'a, V, E, Undir<V,E>>>;
+
+///////////////////////////////////////////////////////////////////////////////
+//  Minimum spanning forests
+///////////////////////////////////////////////////////////////////////////////
+
+/// A graph with all of `g`'s vertices but only the edges in `span`
+fn forest<'a, V, E>(g: &'a UndirectedGraph<V,E>, span: Span<'a,V,E>)
+    -> UndirectedGraph<&'a V, &'a E>
+    where V: NodeT, E: EdgeT + Ord
+{
+    let mut f = Graph::with_capacity(g.order(), span.len());
+    for v in g.vertices() {
+        f.insert_vertex(v.get());
+    }
+    for (l, e, r) in span {
+        f.insert_undirected_edge(e, &l.get(), &r.get());
+    }
+    f
+}
+
+/// Sort the edges and keep every one that joins two trees
+pub(crate) fn kruskal<V, E>(g: &UndirectedGraph<V,E>) -> UndirectedGraph<&V, &E>
+    where V: NodeT, E: EdgeT + Ord
+{
+    let index: HashMap<&V, usize> = g.vertices().enumerate().map(|(i, v)| (v.get(), i)).collect();
+    let mut edges = edge_list(g);
+    edges.sort_by(|a, b| a.1.cmp(b.1));
+    let mut sets = UnionFind::new(g.order());
+    let span = edges.into_iter()
+        .filter(|&(l, _, r)| sets.union(index[l.get()], index[r.get()]))
+        .collect();
+    forest(g, span)
+}
+
+/// Grow each tree from an arbitrary root along its cheapest outgoing edge
+pub(crate) fn prim<V, E>(g: &UndirectedGraph<V,E>) -> UndirectedGraph<&V, &E>
+    where V: NodeT, E: EdgeT + Ord
+{
+    let mut seen: HashSet<&V> = HashSet::with_capacity(g.order());
+    let mut span = Vec::with_capacity(g.order());
+    let mut queue = BinaryHeap::new();
+    for root in g.vertices() {
+        if seen.insert(root.get()) == false {
+            continue;
+        }
+        queue.extend(g.get_neighbors(root).with_edges().map(|(r, e)| MinFirst(e, (root, r))));
+        while let Some(MinFirst(edge, (l, r))) = queue.pop() {
+            if seen.insert(r.get()) == false {
+                continue;
+            }
+            span.push((l, edge, r));
+            queue.extend(g.get_neighbors(r).with_edges()
+                         .filter(|&(n, _)| seen.contains(n.get()) == false)
+                         .map(|(n, e)| MinFirst(e, (r, n))));
+        }
+    }
+    forest(g, span)
+}
+
+/// Join every tree to its cheapest neighbor in rounds until none are left
+pub(crate) fn boruvka<V, E>(g: &UndirectedGraph<V,E>) -> UndirectedGraph<&V, &E>
+    where V: NodeT, E: EdgeT + Ord
+{
+    let index: HashMap<&V, usize> = g.vertices().enumerate().map(|(i, v)| (v.get(), i)).collect();
+    let edges = edge_list(g);
+    let mut sets = UnionFind::new(g.order());
+    let mut span = Vec::with_capacity(g.order());
+    loop {
+        // ties are broken by position so every tree agrees on which edge is cheapest
+        let mut cheapest: Vec<Option<usize>> = vec![None; g.order()];
+        for (i, &(l, e, r)) in edges.iter().enumerate() {
+            let (tl, tr) = (sets.find(index[l.get()]), sets.find(index[r.get()]));
+            if tl == tr { continue }
+            for &t in &[tl, tr] {
+                if cheapest[t].is_none_or(|c| (e, i) < (edges[c].1, c)) {
+                    cheapest[t] = Some(i);
+                }
+            }
+        }
+        let mut merged = false;
+        for i in cheapest.into_iter().flatten() {
+            let (l, e, r) = edges[i];
+            if sets.union(index[l.get()], index[r.get()]) {
+                span.push((l, e, r));
+                merged = true;
+            }
+        }
+        if merged == false { break }
+    }
+    forest(g, span)
+}
@@ -0,0 +1,337 @@
+
+use std::collections::HashMap;
+use std::{ptr, vec};
+
+use {Graph, UndirectedGraph};
+use dir::{DirT, Undir};
+use edge::{EdgeT, WeightT, NumT};
+use path::{Path, Cycle};
+use vertex::{NodeT, Vertex};
+use super::matching::max_weight_mates;
+use super::shortest_path::{dijkstra, ShortestPaths};
+
+type UVertex<V,E> = Vertex<V, E, Undir<V,E>>;
+
+/// An edge from `last` back to `start` that isn't `first`, so a cycle through
+///  two vertices can't use the same undirected edge twice
+fn closing_edges<'a, V, E, D>(g: &'a Graph<V,E,D>,
+                              last: &'a Vertex<V,E,D>,
+                              start: &'a Vertex<V,E,D>,
+                              first: Option<&'a E>)
+    -> impl Iterator<Item=&'a E>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>
+{
+    g.get_reachable(last).with_edges()
+        .filter(move |&(r, e)| r.get() == start.get() && first.is_none_or(|f| ptr::eq(e, f) == false))
+        .map(|(_, e)| e)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Hamiltonian paths
+///////////////////////////////////////////////////////////////////////////////
+
+/// Depth-first search over simple paths, trying the neighbor with the fewest
+///  ways onward first (Warnsdorff's rule) and backing out as soon as some 
+///  unvisited vertex is out of reach. Exponential in the worst case
+pub(crate) fn hamiltonian<V, E, D>(g: &Graph<V,E,D>, closed: bool) -> Option<Path<'_,V,E,D>>
+    where V: NodeT, E: EdgeT, D: DirT<V,E>
+{
+    let verts: Vec<_> = g.vertices().collect();
+    if verts.is_empty() { return None }
+    let index: HashMap<&V, usize> = verts.iter().enumerate().map(|(i, v)| (v.get(), i)).collect();
+    let adj: Vec<Vec<(usize, &E)>> = verts.iter().map(|&v| {
+        g.get_reachable(v).with_edges()
+            .map(|(r, e)| (index[r.get()], e))
+            .filter(|&(j, _)| j != index[v.get()])
+            .collect()
+    }).collect();
+    let n = verts.len();
+    // a cycle can start anywhere; a path should try its likeliest ends first
+    let mut starts: Vec<usize> = if closed { vec![0] } else { (0..n).collect() };
+    starts.sort_by_key(|&i| adj[i].len());
+
+    let mut visited = vec![false; n];
+    let onward = |i: usize, visited: &[bool]| -> vec::IntoIter<(usize, &E)> {
+        let mut next: Vec<_> = adj[i].iter().cloned().filter(|&(j, _)| visited[j] == false).collect();
+        next.sort_by_key(|&(j, _)| adj[j].iter().filter(|&&(k, _)| visited[k] == false).count());
+        next.into_iter()
+    };
+    // whether every unvisited vertex can still be reached from `i`
+    let reaches_rest = |i: usize, visited: &[bool]| {
+        let mut seen = visited.to_vec();
+        let mut stack = vec![i];
+        let mut count = visited.iter().filter(|&&v| v).count();
+        while let Some(j) = stack.pop() {
+            for &(k, _) in &adj[j] {
+                if seen[k] == false {
+                    seen[k] = true;
+                    count += 1;
+                    stack.push(k);
+                }
+            }
+        }
+        count == n
+    };
+    for s in starts {
+        visited[s] = true;
+        let mut route: Vec<(usize, &E)> = vec![];
+        let mut stack = vec![onward(s, &visited)];
+        loop {
+            if route.len() + 1 == n {
+                let last = route.last().map_or(s, |&(j, _)| j);
+                let first = route.first().map(|&(_, e)| e);
+                let close = closing_edges(g, verts[last], verts[s], first).next();
+                if closed == false || close.is_some() {
+                    let mut path = Path::new(verts[s]);
+                    for &(j, e) in &route {
+                        path.push(e, verts[j]);
+                    }
+                    if let Some(e) = close.filter(|_| closed) {
+                        path.push(e, verts[s]);
+                    }
+                    return Some(path);
+                }
+            }
+            match stack.last_mut().and_then(|c| c.next()) {
+                Some((j, e)) => {
+                    visited[j] = true;
+                    route.push((j, e));
+                    if reaches_rest(j, &visited) {
+                        stack.push(onward(j, &visited));
+                    } else {
+                        stack.push(vec![].into_iter());
+                    }
+                }
+                None => {
+                    stack.pop();
+                    match route.pop() {
+                        Some((j, _)) => visited[j] = false,
+                        None => break,
+                    }
+                }
+            }
+        }
+        visited[s] = false;
+    }
+    None
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Held-Karp
+///////////////////////////////////////////////////////////////////////////////
+
+/// Most vertices Held-Karp will take: its table has `n·2ⁿ` entries, which
+///  is already hundreds of megabytes at 20
+const HELD_KARP_MAX: usize = 20;
+
+/// Lightest cycle through every vertex, by dynamic programming over subsets
+///  of vertices. O(2ⁿn²) time and O(2ⁿn) space, so only for small graphs;
+///  `None` past `HELD_KARP_MAX` vertices
+pub(crate) fn held_karp<V, E, D>(g: &Graph<V,E,D>) -> Option<Cycle<'_,V,E,D>>
+    where V: NodeT, E: WeightT, D: DirT<V,E>
+{
+    let verts: Vec<_> = g.vertices().collect();
+    let n = verts.len();
+    if n == 0 || n > HELD_KARP_MAX { return None }
+    let index: HashMap<&V, usize> = verts.iter().enumerate().map(|(i, v)| (v.get(), i)).collect();
+    // the lightest of any parallel edges
+    let mut cost: Vec<Option<&E>> = vec![None; n * n];
+    for (i, &v) in verts.iter().enumerate() {
+        for (r, e) in g.get_reachable(v).with_edges() {
+            let j = index[r.get()];
+            if i != j && cost[i * n + j].is_none_or(|c| e.weight() < c.weight()) {
+                cost[i * n + j] = Some(e);
+            }
+        }
+    }
+
+    // `best[set * n + j]`: the lightest path from vertex 0 through `set`
+    //  (which holds both) ending at `j`, and the vertex before `j` on it
+    let mut best: Vec<Option<(E::W, usize)>> = vec![None; n << n];
+    best[n] = Some((E::W::zero(), 0));
+    for set in (1 .. 1 << n).step_by(2) {
+        for j in 0..n {
+            let here = match best[set * n + j] {
+                Some((w, _)) => w,
+                None => continue,
+            };
+            for k in (0..n).filter(|&k| set & (1 << k) == 0) {
+                if let Some(e) = cost[j * n + k] {
+                    let (slot, alt) = ((set | 1 << k) * n + k, here + e.weight());
+                    if best[slot].is_none_or(|(w, _)| alt < w) {
+                        best[slot] = Some((alt, j));
+                    }
+                }
+            }
+        }
+    }
+
+    // close the cycle from whichever end is lightest overall
+    let full = (1 << n) - 1;
+    let first = |j: usize| if n == 2 { cost[j] } else { None };
+    let (_, last, close) = (0..n)
+        .filter_map(|j| {
+            let (w, _) = best[full * n + j]?;
+            let close = closing_edges(g, verts[j], verts[0], first(j))
+                .min_by_key(|e| e.weight())?;
+            Some((w + close.weight(), j, close))
+        })
+        .min_by_key(|&(w, _, _)| w)?;
+    let mut steps = vec![];
+    let (mut set, mut j) = (full, last);
+    while j != 0 || set != 1 {
+        let (_, prev) = best[set * n + j].unwrap();
+        steps.push((verts[prev], cost[prev * n + j].unwrap()));
+        set &= !(1 << j);
+        j = prev;
+    }
+    let mut path = Path::from_reversed(verts[last], steps);
+    path.push(close, verts[0]);
+    Some(Cycle::new(path))
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Approximate tours
+///////////////////////////////////////////////////////////////////////////////
+
+/// Shortest distances between every pair of vertices, which a tour uses as
+///  if every pair were adjacent
+struct Metric<'a, V: 'a+NodeT, E: 'a+WeightT> {
+    verts: Vec<&'a UVertex<V,E>>,
+    trees: Vec<ShortestPaths<'a, V, E, Undir<V,E>, E::W>>,
+    dist: Vec<E::W>,
+}
+
+impl<'a, V: NodeT, E: WeightT> Metric<'a,V,E> {
+    /// `None` if some pair isn't connected
+    fn new(g: &'a UndirectedGraph<V,E>) -> Option<Self> {
+        let verts: Vec<_> = g.vertices().collect();
+        let trees: Vec<_> = verts.iter().map(|&v| dijkstra(g, v)).collect();
+        let mut dist = Vec::with_capacity(verts.len() * verts.len());
+        for sp in &trees {
+            for &r in &verts {
+                dist.push(sp.distance(r.get())?);
+            }
+        }
+        Some(Metric { verts, trees, dist })
+    }
+    fn d(&self, i: usize, j: usize) -> E::W {
+        self.dist[i * self.verts.len() + j]
+    }
+    /// Join consecutive stops (and the last to the first) by shortest paths
+    fn walk(&self, order: &[usize]) -> Path<'a, V, E, Undir<V,E>> {
+        let mut walk = Path::new(self.verts[order[0]]);
+        for i in 0 .. order.len() {
+            let (l, r) = (order[i], order[(i + 1) % order.len()]);
+            if l == r { continue }
+            let leg = self.trees[l].path_to(self.verts[r].get()).expect("metric pairs are connected");
+            for (_, e, next) in leg.steps() {
+                walk.push(e, next);
+            }
+        }
+        walk
+    }
+}
+
+/// Start from the nearest-neighbor tour and keep uncrossing pairs of legs
+///  while that makes it shorter
+pub(crate) fn two_opt<V, E>(g: &UndirectedGraph<V,E>) -> Option<Path<'_, V, E, Undir<V,E>>>
+    where V: NodeT, E: WeightT
+{
+    let m = Metric::new(g)?;
+    let n = m.verts.len();
+    if n == 0 { return None }
+    let mut order = vec![0];
+    let mut left: Vec<usize> = (1..n).collect();
+    while left.is_empty() == false {
+        let here = order[order.len() - 1];
+        let (i, _) = left.iter().enumerate().min_by_key(|&(_, &j)| m.d(here, j)).unwrap();
+        order.push(left.swap_remove(i));
+    }
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for i in 0 .. n.saturating_sub(2) {
+            for j in i + 2 .. n {
+                let (a, b, c, d) = (order[i], order[i + 1], order[j], order[(j + 1) % n]);
+                if a == d { continue }
+                if m.d(a, c) + m.d(b, d) < m.d(a, b) + m.d(c, d) {
+                    order[i + 1 ..= j].reverse();
+                    improved = true;
+                }
+            }
+        }
+    }
+    Some(m.walk(&order))
+}
+
+/// Join a minimum spanning tree's odd vertices in a minimum-weight perfect
+///  matching, then shortcut an Eulerian circuit of the result; at most 1.5
+///  times as long as the optimal tour
+pub(crate) fn christofides<V, E>(g: &UndirectedGraph<V,E>) -> Option<Path<'_, V, E, Undir<V,E>>>
+    where V: NodeT, E: WeightT
+{
+    let m = Metric::new(g)?;
+    let n = m.verts.len();
+    if n == 0 { return None }
+    let mut multi: Vec<Vec<usize>> = vec![vec![]; n];
+
+    // Prim's on the dense metric
+    let mut near: Vec<Option<(E::W, usize)>> = vec![None; n];
+    let mut in_tree = vec![false; n];
+    let mut cur = 0;
+    for _ in 1..n {
+        in_tree[cur] = true;
+        for j in (0..n).filter(|&j| in_tree[j] == false) {
+            if near[j].is_none_or(|(w, _)| m.d(cur, j) < w) {
+                near[j] = Some((m.d(cur, j), cur));
+            }
+        }
+        let next = (0..n).filter(|&j| in_tree[j] == false)
+            .min_by_key(|&j| near[j].map(|(w, _)| w))
+            .unwrap();
+        let (_, parent) = near[next].unwrap();
+        multi[next].push(parent);
+        multi[parent].push(next);
+        cur = next;
+    }
+
+    // the heaviest matching of `big - d` is the lightest perfect one, as long
+    //  as `big` outweighs every pair's share of a perfect matching's distance
+    let odd: Vec<usize> = (0..n).filter(|&i| multi[i].len() % 2 == 1).collect();
+    //  (worked out in `i128`, since `big` could overflow the weights' type)
+    let max = m.dist.iter().cloned().max().map_or(0, NumT::to_i128);
+    let big = 1 + (odd.len() / 2) as i128 * max;
+    let mut pairs = vec![];
+    for a in 0 .. odd.len() {
+        for b in a + 1 .. odd.len() {
+            pairs.push((a, b, big - m.d(odd[a], odd[b]).to_i128()));
+        }
+    }
+    for (a, mate) in max_weight_mates(odd.len(), pairs).into_iter().enumerate() {
+        let b = mate.expect("every odd vertex is matched");
+        multi[odd[a]].push(odd[b]);
+    }
+
+    // Hierholzer, skipping vertices already on the tour
+    let mut order = Vec::with_capacity(n);
+    let mut seen = vec![false; n];
+    let mut stack = vec![0];
+    while let Some(&v) = stack.last() {
+        match multi[v].pop() {
+            Some(w) => {
+                let back = multi[w].iter().position(|&x| x == v).unwrap();
+                multi[w].swap_remove(back);
+                stack.push(w);
+            }
+            None => {
+                stack.pop();
+                if seen[v] == false {
+                    seen[v] = true;
+                    order.push(v);
+                }
+            }
+        }
+    }
+    Some(m.walk(&order))
+}
@@ -0,0 +1,71 @@
+
+use std::collections::HashMap;
+
+use UndirectedGraph;
+use edge::EdgeT;
+use vertex::NodeT;
+use super::Neighborhoods;
+
+/// Number of triangles through each vertex. Each edge is pointed from the
+///  endpoint of lower degree, so every triangle is found exactly once (from
+///  its lowest vertex) and no vertex has more than O(√m) edges out, for
+///  O(m√m) overall. Self-loops and parallel edges make no extra triangles
+fn count<V: NodeT, E: EdgeT>(hoods: &Neighborhoods<'_,V,E>) -> Vec<usize> {
+    let n = hoods.verts.len();
+    let rank = |i: usize| (hoods.hoods[i].len(), i);
+    let higher: Vec<Vec<usize>> = (0 .. n)
+        .map(|i| hoods.hoods[i].iter().cloned().filter(|&j| rank(j) > rank(i)).collect())
+        .collect();
+    let mut through = vec![0; n];
+    for i in 0 .. n {
+        for &j in &higher[i] {
+            for &k in &higher[j] {
+                if hoods.hoods[i].contains(&k) {
+                    through[i] += 1;
+                    through[j] += 1;
+                    through[k] += 1;
+                }
+            }
+        }
+    }
+    through
+}
+
+pub(crate) fn triangles<V, E>(g: &UndirectedGraph<V,E>) -> HashMap<&V, usize>
+    where V: NodeT, E: EdgeT
+{
+    let hoods = Neighborhoods::new(g);
+    let through = count(&hoods);
+    hoods.verts.iter().zip(through).map(|(v, t)| (v.get(), t)).collect()
+}
+
+pub(crate) fn triangle_count<V, E>(g: &UndirectedGraph<V,E>) -> usize
+    where V: NodeT, E: EdgeT
+{
+    count(&Neighborhoods::new(g)).iter().sum::<usize>() / 3
+}
+
+/// Fraction of the pairs of each vertex's neighbors that are adjacent
+///  themselves; zero for vertices with fewer than two neighbors
+pub(crate) fn clustering<V, E>(g: &UndirectedGraph<V,E>) -> HashMap<&V, f64>
+    where V: NodeT, E: EdgeT
+{
+    let hoods = Neighborhoods::new(g);
+    let through = count(&hoods);
+    hoods.verts.iter().zip(&hoods.hoods).zip(through).map(|((v, hood), t)| {
+        let d = hood.len();
+        let pairs = d * d.saturating_sub(1) / 2;
+        (v.get(), if pairs == 0 { 0.0 } else { t as f64 / pairs as f64 })
+    }).collect()
+}
+
+/// Fraction of paths of length two whose ends are adjacent, closing them
+///  into triangles
+pub(crate) fn transitivity<V, E>(g: &UndirectedGraph<V,E>) -> f64
+    where V: NodeT, E: EdgeT
+{
+    let hoods = Neighborhoods::new(g);
+    let closed: usize = count(&hoods).iter().sum();
+    let paths: usize = hoods.hoods.iter().map(|h| h.len() * h.len().saturating_sub(1) / 2).sum();
+    if paths == 0 { 0.0 } else { closed as f64 / paths as f64 }
+}
@@ -0,0 +1,46 @@
+
+///////////////////////////////////////////////////////////////////////////////
+//  Union-Find
+///////////////////////////////////////////////////////////////////////////////
+
+/// Disjoint sets over `0..n` with path compression and union by rank
+#[derive(Debug)]
+pub(crate) struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> Self {
+        UnionFind { parent: (0..n).collect(), rank: vec![0; n] }
+    }
+    pub fn find(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut cur = i;
+        while self.parent[cur] != root {
+            let next = self.parent[cur];
+            self.parent[cur] = root;
+            cur = next;
+        }
+        root
+    }
+    /// Merge the sets containing `i` and `j`; false if they were already one
+    pub fn union(&mut self, i: usize, j: usize) -> bool {
+        let (ri, rj) = (self.find(i), self.find(j));
+        if ri == rj {
+            return false;
+        }
+        if self.rank[ri] < self.rank[rj] {
+            self.parent[ri] = rj;
+        } else {
+            self.parent[rj] = ri;
+            if self.rank[ri] == self.rank[rj] {
+                self.rank[ri] += 1;
+            }
+        }
+        true
+    }
+}
@@ -1,5 +1,6 @@
 
 use std::fmt;
+use std::ptr;
 
 use edge::{EdgeT, GenEdge, DirEdge, UndirEdge};
 use vertex::NodeT;
@@ -9,11 +10,27 @@ use vertex::NodeT;
 ///////////////////////////////////////////////////////////////////////////////
 
 pub trait DirT<V: NodeT, E: EdgeT>: fmt::Debug + Sized {
+    /// Whether an edge only leads from its source to its destination
+    const DIRECTED: bool;
     fn new() -> Self;
     fn degree(&self) -> usize;
     fn push_src(&mut self, edge: GenEdge<V, E, Self>);
     fn push_dst(&mut self, edge: GenEdge<V, E, Self>);
     fn get_reachable(&self) -> &[GenEdge<V, E, Self>];
+    /// Edges that only lead here, so can only be followed against their
+    ///  direction; none for an undirected graph
+    fn get_backward(&self) -> &[GenEdge<V, E, Self>];
+    /// Unlink one entry sharing the allocation of `edge` (compared by address)
+    fn remove(&mut self, edge: &E) -> Option<GenEdge<V, E, Self>>;
+    /// Unlink every entry in the neighborhood
+    fn drain(&mut self) -> Vec<GenEdge<V, E, Self>>;
+}
+
+fn remove_from<V: NodeT, E: EdgeT, D: DirT<V,E>>(v: &mut Vec<GenEdge<V,E,D>>, edge: &E)
+    -> Option<GenEdge<V,E,D>>
+{
+    let i = v.iter().position(|e| ptr::eq(e.as_ref(), edge))?;
+    Some(v.remove(i))
 }
 
 #[derive(Debug)] 
@@ -28,19 +45,38 @@ pub struct Undir<V: NodeT, E: EdgeT> {
 }
 
 impl<V: NodeT, E: EdgeT> DirT<V,E> for Dir<V,E> {
+    const DIRECTED: bool = true;
     fn new() -> Self { Dir { children: vec![], parents: vec![] } }
     fn degree(&self) -> usize { self.children.len() + self.parents.len() }
     fn push_src(&mut self, edge: GenEdge<V, E, Self>) { self.parents.push(edge); }
     //fn push_dst(&mut self, _edge: GenEdge<V, E, Self>) { /*self.children.push(edge);*/ } // uhhh
     fn push_dst(&mut self, _edge: GenEdge<V, E, Self>) { self.children.push(_edge); } // uh
     fn get_reachable(&self) -> &[GenEdge<V, E, Self>] { self.get_children() }
+    fn get_backward(&self) -> &[GenEdge<V, E, Self>] { self.get_parents() }
+    fn remove(&mut self, edge: &E) -> Option<GenEdge<V, E, Self>> {
+        remove_from(&mut self.children, edge)
+            .or_else(|| remove_from(&mut self.parents, edge))
+    }
+    fn drain(&mut self) -> Vec<GenEdge<V, E, Self>> {
+        let mut all: Vec<_> = self.children.drain(..).collect();
+        all.append(&mut self.parents);
+        all
+    }
 }
 impl<V: NodeT, E: EdgeT> DirT<V,E> for Undir<V,E> {
+    const DIRECTED: bool = false;
     fn new() -> Self { Undir { neighbors: vec![] } }
     fn degree(&self) -> usize { self.neighbors.len() }
     fn push_src(&mut self, edge: GenEdge<V, E, Self>) { self.neighbors.push(edge); }
     fn push_dst(&mut self, edge: GenEdge<V, E, Self>) { self.neighbors.push(edge); }
     fn get_reachable(&self) -> &[GenEdge<V, E, Self>] { self.get_neighbors() }
+    fn get_backward(&self) -> &[GenEdge<V, E, Self>] { &[] }
+    fn remove(&mut self, edge: &E) -> Option<GenEdge<V, E, Self>> {
+        remove_from(&mut self.neighbors, edge)
+    }
+    fn drain(&mut self) -> Vec<GenEdge<V, E, Self>> {
+        self.neighbors.drain(..).collect()
+    }
 }
 
 impl<V: NodeT, E: EdgeT> Dir<V,E> {
@@ -2,6 +2,7 @@
 use std::fmt;
 use std::rc::Rc;
 use std::marker::PhantomData;
+use std::ops::{Add, Sub, Mul, Div};
 
 use dir::{DirT, Dir, Undir};
 use vertex::{NodeT};
@@ -17,6 +18,87 @@ impl<T: fmt::Debug + Ord> EdgeT for T {}
 pub struct UnweightedEdge;
 impl EdgeT for UnweightedEdge {}
 
+/// Numeric type that edge weights / path lengths are measured in
+pub trait NumT: fmt::Debug + Copy + Ord
+    + Add<Output=Self> + Sub<Output=Self> + Mul<Output=Self> + Div<Output=Self>
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    /// For algorithms that need fractions, like the probabilities of PageRank
+    fn to_f64(self) -> f64;
+    /// Exact for every integer type, for sums that could overflow `Self`
+    fn to_i128(self) -> i128;
+}
+
+/// An edge whose value can be read as a length
+pub trait WeightT: EdgeT {
+    /// What lengths are measured and summed in. 8- and 16-bit integers are
+    ///  widened to 64 bits so no path of them can overflow; wider ones are
+    ///  summed as themselves, so a path longer than their maximum overflows
+    type W: NumT;
+    fn weight(&self) -> Self::W;
+}
+
+/// An edge that can carry up to some amount of flow
+pub trait CapacityT: EdgeT {
+    type W: NumT;
+    fn capacity(&self) -> Self::W;
+}
+
+/// An edge with a capacity and a cost per unit of flow; costs must be signed
+///  since pushing flow back along an edge refunds its cost. As with `WeightT`,
+///  8- and 16-bit pairs are measured in 64 bits so total costs can't overflow
+pub trait CostT: CapacityT {
+    fn cost(&self) -> Self::W;
+}
+
+macro_rules! impl_num {
+    ($($t:ty),*) => { $(
+        impl NumT for $t {
+            fn zero() -> Self { 0 }
+            fn one() -> Self { 1 }
+            fn to_f64(self) -> f64 { self as f64 }
+            fn to_i128(self) -> i128 { self as i128 }
+        }
+        impl CapacityT for $t {
+            type W = $t;
+            fn capacity(&self) -> $t { *self }
+        }
+    )* }
+}
+impl_num!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+macro_rules! impl_weight {
+    ($($t:ty => $w:ty),*) => { $(
+        impl WeightT for $t {
+            type W = $w;
+            fn weight(&self) -> $w { *self as $w }
+        }
+    )* }
+}
+impl_weight!(u8 => u64, u16 => u64, i8 => i64, i16 => i64);
+impl_weight!(u32 => u32, u64 => u64, usize => usize, i32 => i32, i64 => i64, isize => isize);
+
+// `(capacity, cost)`
+macro_rules! impl_cost {
+    ($($t:ty => $w:ty),*) => { $(
+        impl CapacityT for ($t, $t) {
+            type W = $w;
+            fn capacity(&self) -> $w { self.0 as $w }
+        }
+        impl CostT for ($t, $t) {
+            fn cost(&self) -> $w { self.1 as $w }
+        }
+    )* }
+}
+impl_cost!(i8 => i64, i16 => i64);
+impl_cost!(i32 => i32, i64 => i64, isize => isize);
+
+impl WeightT for UnweightedEdge {
+    type W = usize;
+    fn weight(&self) -> usize { 1 }
+}
+
 pub type DirEdge<V,E>   = Edge<V, E, Dir<V,E>>;
 pub type UndirEdge<V,E> = Edge<V, E, Undir<V,E>>;
 pub type GenEdge<V,E,D> = Edge<V, E, D>;
@@ -1,10 +1,12 @@
 
-use std::collections::HashSet;
+use std::cmp;
+use std::collections::{HashMap, HashSet};
 
-use Graph;
-use dir::{DirT};
+use {Graph, DiGraph};
+use dir::{DirT, Dir};
 use edge::{EdgeT};
 use vertex::{NodeT, Vertex};
+use super::{Neighbors, Vertices};
 
 //pub type Component<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<E>> = Vec<&'a Vertex<V,E,D>>;
 pub type Component<'a, V, E, D> = Vec<&'a Vertex<V,E,D>>;
@@ -37,3 +39,124 @@ impl<'a, V: NodeT, E: EdgeT, D: DirT<V,E>> Iterator for Components<'a,V,E,D> {
     }
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// WEAK COMPONENTS
+///////////////////////////////////////////////////////////////////////////////
+
+/// Components of a `DiGraph` when edge direction is ignored
+pub struct WeakComponents<'a, V: 'a+NodeT, E: 'a+EdgeT> {
+    graph: &'a DiGraph<V,E>,
+    roots: Vertices<'a, V, E, Dir<V,E>>,
+    seen: HashSet<&'a V>,
+}
+
+impl<'a, V: NodeT, E: EdgeT> WeakComponents<'a,V,E> {
+    pub(crate) fn new(g: &'a DiGraph<V,E>) -> Self {
+        WeakComponents { graph: g, roots: g.vertices(), seen: HashSet::with_capacity(g.order()) }
+    }
+}
+
+impl<'a, V: NodeT, E: EdgeT> Iterator for WeakComponents<'a,V,E> {
+    type Item = Component<'a, V, E, Dir<V,E>>;
+    fn next(&mut self) -> Option<Component<'a, V, E, Dir<V,E>>> {
+        let seen = &mut self.seen;
+        let start = self.roots.by_ref().find(|r| seen.contains(r.get()) == false)?;
+        seen.insert(start.get());
+        let mut component = vec![start];
+        let mut stack = vec![start];
+        while let Some(cur) = stack.pop() {
+            // parents and children alike
+            for n in self.graph.get_neighbors(cur) {
+                if seen.insert(n.get()) {
+                    component.push(n);
+                    stack.push(n);
+                }
+            }
+        }
+        Some(component)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// STRONG COMPONENTS
+///////////////////////////////////////////////////////////////////////////////
+
+type DVertex<V,E> = Vertex<V, E, Dir<V,E>>;
+
+/// Tarjan's algorithm; components are yielded in reverse topological order
+///  (nothing in a component reaches a component yielded after it)
+pub struct StrongComponents<'a, V: 'a+NodeT, E: 'a+EdgeT> {
+    graph: &'a DiGraph<V,E>,
+    roots: Vertices<'a, V, E, Dir<V,E>>,
+    index: HashMap<&'a V, usize>,
+    low: HashMap<&'a V, usize>,
+    stack: Vec<&'a DVertex<V,E>>,
+    on_stack: HashSet<&'a V>,
+    // explicit call stack for the depth-first search
+    calls: Vec<(&'a DVertex<V,E>, Neighbors<'a, V, E, Dir<V,E>>)>,
+}
+
+impl<'a, V: NodeT, E: EdgeT> StrongComponents<'a,V,E> {
+    pub(crate) fn new(g: &'a DiGraph<V,E>) -> Self {
+        StrongComponents {
+            graph: g,
+            roots: g.vertices(),
+            index: HashMap::with_capacity(g.order()),
+            low: HashMap::with_capacity(g.order()),
+            stack: vec![],
+            on_stack: HashSet::new(),
+            calls: vec![],
+        }
+    }
+    fn visit(&mut self, v: &'a DVertex<V,E>) {
+        let i = self.index.len();
+        self.index.insert(v.get(), i);
+        self.low.insert(v.get(), i);
+        self.stack.push(v);
+        self.on_stack.insert(v.get());
+        self.calls.push((v, self.graph.get_children(v)));
+    }
+    fn lower(&mut self, v: &'a V, to: usize) {
+        let low = self.low.get_mut(v).expect("lowering unvisited vertex");
+        *low = cmp::min(*low, to);
+    }
+}
+
+impl<'a, V: NodeT, E: EdgeT> Iterator for StrongComponents<'a,V,E> {
+    type Item = Component<'a, V, E, Dir<V,E>>;
+    fn next(&mut self) -> Option<Component<'a, V, E, Dir<V,E>>> {
+        loop {
+            if self.calls.is_empty() {
+                let index = &self.index;
+                let root = self.roots.by_ref().find(|r| index.contains_key(r.get()) == false)?;
+                self.visit(root);
+            }
+            while let Some((v, child)) = self.calls.last_mut().map(|c| (c.0, c.1.next())) {
+                match child {
+                    Some(w) if self.index.contains_key(w.get()) == false => self.visit(w),
+                    Some(w) => if self.on_stack.contains(w.get()) {
+                        let to = self.index[w.get()];
+                        self.lower(v.get(), to);
+                    },
+                    None => {
+                        self.calls.pop();
+                        let low = self.low[v.get()];
+                        if let Some(&(parent, _)) = self.calls.last() {
+                            self.lower(parent.get(), low);
+                        }
+                        if low == self.index[v.get()] {
+                            let mut component = vec![];
+                            loop {
+                                let w = self.stack.pop().expect("tarjan stack underflow");
+                                self.on_stack.remove(w.get());
+                                component.push(w);
+                                if w.get() == v.get() { break }
+                            }
+                            return Some(component);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
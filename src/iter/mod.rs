@@ -10,10 +10,13 @@ use edge::{EdgeT};
 use vertex::{NodeT, Vertex};
 
 mod neighbors;
-pub use self::neighbors::Neighbors;
+pub use self::neighbors::{Neighbors, NeighborEdges};
 
 mod components;
-pub use self::components::Components;
+pub use self::components::{Components, WeakComponents, StrongComponents};
+
+mod topological;
+pub use self::topological::Topological;
 
 ///////////////////////////////////////////////////////////////////////////////
 // VERTICES
@@ -45,6 +48,9 @@ pub struct BreadthFirst<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>> {
     seen: HashSet<&'a V>,
     this: VecDeque<&'a Vertex<V,E,D>>,
     next: VecDeque<&'a Vertex<V,E,D>>,
+    depth: usize,
+    // follow edges against their direction too
+    both_ways: bool,
 }
 
 impl<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>> BreadthFirst<'a,V,E,D> {
@@ -55,7 +61,18 @@ impl<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>> BreadthFirst<'a,V,E,D> {
             queue.push_back(first);
             seen.insert(first.borrow());
         }
-        BreadthFirst { graph: g, this: queue, next: VecDeque::new(), seen, }
+        BreadthFirst { graph: g, this: queue, next: VecDeque::new(), seen, depth: 0, both_ways: false }
+    }
+    /// Search as though every edge were undirected
+    pub(crate) fn both_ways(g: &'a Graph<V,E,D>, start: Option<&'a Vertex<V,E,D>>) -> Self {
+        BreadthFirst { both_ways: true, ..BreadthFirst::new(g, start) }
+    }
+}
+
+impl<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>> BreadthFirst<'a,V,E,D> {
+    /// Distance from the start to the vertex most recently yielded
+    pub fn depth(&self) -> usize {
+        self.depth
     }
 }
 
@@ -63,7 +80,12 @@ impl<'a, V: NodeT, E: EdgeT, D: DirT<V,E>> Iterator for BreadthFirst<'a,V,E,D> {
     type Item = &'a Vertex<V,E,D>;
     fn next(&mut self) -> Option<&'a Vertex<V,E,D>> {
         if let Some(cur) = self.this.pop_front() {
-            for reachable in self.graph.get_reachable(cur) {
+            let hood = if self.both_ways {
+                self.graph.get_adjacent(cur)
+            } else {
+                self.graph.get_reachable(cur)
+            };
+            for reachable in hood {
                 let val: &V = reachable.as_ref();
                 if self.seen.contains(val) == false {
                     self.seen.insert(val);
@@ -76,6 +98,7 @@ impl<'a, V: NodeT, E: EdgeT, D: DirT<V,E>> Iterator for BreadthFirst<'a,V,E,D> {
         } else {
             mem::swap(&mut self.this, &mut self.next);
             self.next.clear();
+            self.depth += 1;
             self.next()
         }
     }
@@ -104,7 +127,7 @@ impl<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>> DepthFirst<'a,V,E,D> {
 impl<'a, V: NodeT, E: EdgeT, D: DirT<V,E>> Iterator for DepthFirst<'a,V,E,D> {
     type Item = &'a Vertex<V,E,D>;
     fn next(&mut self) -> Option<&'a Vertex<V,E,D>> {
-        self.stack.pop().map(|next| {
+        self.stack.pop().inspect(|&next| {
             let v = next.as_ref();
             if self.seen.contains(v) == false {
                 self.seen.insert(v);
@@ -114,7 +137,6 @@ impl<'a, V: NodeT, E: EdgeT, D: DirT<V,E>> Iterator for DepthFirst<'a,V,E,D> {
                     }
                 });
             }
-            next
         })
     }
 }
@@ -16,6 +16,10 @@ use vertex::{NodeT, Vertex};
 enum NSet<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>> {
     // either
     Reachable(Iter<'a, GenEdge<V,E,D>>),
+    Adjacent {
+        backward: Iter<'a, GenEdge<V,E,D>>,
+        reachable: Iter<'a, GenEdge<V,E,D>>,
+    },
     // undirected
     Neighbors(Iter<'a, UndirEdge<V,E>>),
     // directed
@@ -35,15 +39,42 @@ pub struct Neighbors<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>> {
 impl<'a, V: NodeT, E: EdgeT, D: DirT<V,E>> Iterator for Neighbors<'a,V,E,D> {
     type Item = &'a Vertex<V,E,D>;
     fn next(&mut self) -> Option<&'a Vertex<V,E,D>> {
-        let other_v: Option<&'a V> = match self.kind {
-            NSet::Reachable(ref mut r) => r.next().map(Edge::get_end),
-            NSet::Neighbors(ref mut n) => n.next().map(Edge::get_end),
+        self.next_edge().map(|(v, _)| v)
+    }
+}
+
+impl<'a, V: NodeT, E: EdgeT, D: DirT<V,E>> Neighbors<'a,V,E,D> {
+    /// Also yield the edge each neighbor was reached by
+    pub fn with_edges(self) -> NeighborEdges<'a,V,E,D> {
+        NeighborEdges { inner: self }
+    }
+    fn next_edge(&mut self) -> Option<(&'a Vertex<V,E,D>, &'a E)> {
+        fn split<V: NodeT, E: EdgeT, D: DirT<V,E>>(e: &Edge<V,E,D>) -> (&V, &E) {
+            (e.get_end(), e.as_ref())
+        }
+        let other: Option<(&'a V, &'a E)> = match self.kind {
+            NSet::Reachable(ref mut r) => r.next().map(split),
+            NSet::Adjacent { ref mut backward, ref mut reachable } =>
+                backward.next().or_else(|| reachable.next()).map(split),
+            NSet::Neighbors(ref mut n) => n.next().map(split),
             | NSet::Parents( ref mut d) 
-            | NSet::Children(ref mut d) => d.next().map(Edge::get_end),
+            | NSet::Children(ref mut d) => d.next().map(split),
             NSet::Both { ref mut parents, ref mut children } => 
-                parents.next().or_else(|| children.next()).map(Edge::get_end),
+                parents.next().or_else(|| children.next()).map(split),
         };
-        other_v.and_then(|v| self.graph.get_vertex(v))
+        let graph = self.graph;
+        other.and_then(|(v, e)| graph.get_vertex(v).map(|v| (v, e)))
+    }
+}
+
+pub struct NeighborEdges<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>> {
+    inner: Neighbors<'a,V,E,D>,
+}
+
+impl<'a, V: NodeT, E: EdgeT, D: DirT<V,E>> Iterator for NeighborEdges<'a,V,E,D> {
+    type Item = (&'a Vertex<V,E,D>, &'a E);
+    fn next(&mut self) -> Option<(&'a Vertex<V,E,D>, &'a E)> {
+        self.inner.next_edge()
     }
 }
 
@@ -54,6 +85,13 @@ impl<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>> Neighbors<'a,V,E,D> {
     {
         Neighbors { graph: g, kind: NSet::Reachable(r) }
     }
+    pub(crate) fn adjacent(g: &'a Graph<V,E,D>, 
+                           b: slice::Iter<'a, GenEdge<V,E,D>>,
+                           r: slice::Iter<'a, GenEdge<V,E,D>>)
+        -> Self
+    {
+        Neighbors { graph: g, kind: NSet::Adjacent { backward: b, reachable: r } }
+    }
 }
 
 impl<'a, V: 'a+NodeT, E: 'a+EdgeT> Neighbors<'a, V, E, Undir<V,E>> {
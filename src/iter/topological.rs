@@ -0,0 +1,82 @@
+
+use std::collections::{HashMap, VecDeque};
+
+use DiGraph;
+use dir::Dir;
+use edge::EdgeT;
+use path::{Path, Cycle};
+use vertex::{NodeT, Vertex};
+
+type DVertex<V,E> = Vertex<V, E, Dir<V,E>>;
+
+///////////////////////////////////////////////////////////////////////////////
+// TOPOLOGICAL
+///////////////////////////////////////////////////////////////////////////////
+
+/// Kahn's algorithm: yields a vertex once all of its parents have been yielded
+pub struct Topological<'a, V: 'a+NodeT, E: 'a+EdgeT> {
+    graph: &'a DiGraph<V,E>,
+    // parents not yet yielded, for vertices that aren't ready yet
+    blocked: HashMap<&'a V, usize>,
+    ready: VecDeque<&'a DVertex<V,E>>,
+}
+
+impl<'a, V: NodeT, E: EdgeT> Topological<'a,V,E> {
+    pub(crate) fn new(g: &'a DiGraph<V,E>) -> Self {
+        let mut blocked = HashMap::with_capacity(g.order());
+        let mut ready = VecDeque::new();
+        for v in g.vertices() {
+            match g.get_parents(v).count() {
+                0 => ready.push_back(v),
+                n => { blocked.insert(v.get(), n); }
+            }
+        }
+        Topological { graph: g, blocked, ready }
+    }
+
+    /// After the iterator is exhausted, a cycle through the vertices it
+    ///  never reached (or `None` if it reached them all)
+    pub fn find_cycle(&self) -> Option<Cycle<'a, V, E, Dir<V,E>>> {
+        if self.ready.is_empty() == false { return None }
+        let (&start, _) = self.blocked.iter().next()?;
+        // every blocked vertex has a blocked parent, so walk up until repeating
+        let mut walk = vec![self.graph.get_vertex(start)?];
+        let mut edges = vec![];
+        let mut seen: HashMap<&V, usize> = HashMap::new();
+        seen.insert(start, 0);
+        loop {
+            let cur = walk[walk.len() - 1];
+            let (parent, edge) = self.graph.get_parents(cur).with_edges()
+                .find(|&(p, _)| self.blocked.contains_key(p.get()))?;
+            walk.push(parent);
+            edges.push(edge);
+            if let Some(&j) = seen.get(parent.get()) {
+                // `edges[i]` runs from `walk[i+1]` to `walk[i]`
+                let mut path = Path::new(parent);
+                for i in (j .. edges.len()).rev() {
+                    path.push(edges[i], walk[i]);
+                }
+                return Some(Cycle::new(path));
+            }
+            seen.insert(parent.get(), walk.len() - 1);
+        }
+    }
+}
+
+impl<'a, V: NodeT, E: EdgeT> Iterator for Topological<'a,V,E> {
+    type Item = &'a DVertex<V,E>;
+    fn next(&mut self) -> Option<&'a DVertex<V,E>> {
+        let cur = self.ready.pop_front()?;
+        for child in self.graph.get_children(cur) {
+            let now_ready = match self.blocked.get_mut(child.get()) {
+                Some(n) => { *n -= 1; *n == 0 },
+                None => false,
+            };
+            if now_ready {
+                self.blocked.remove(child.get());
+                self.ready.push_back(child);
+            }
+        }
+        Some(cur)
+    }
+}
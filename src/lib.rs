@@ -1,14 +1,12 @@
 #![allow(unused)]
-#![allow(unknown_lints, bool_comparison, new_without_default_derive)]
+#![allow(unknown_lints, clippy::bool_comparison, clippy::new_without_default, clippy::type_complexity)]
 // `x == false` is better than `!x` and I'll fight anyone who disagrees
 
 /* TODO
  *  Tree (should be easy, try to make it easily ↔ Graph
- *  Path/path stuff for ret vals
  *  CopyGraph: cheaper alternative when T:Copy w/ fewer allocs
  *  quickcheck tests, mutate, quickcheck alt?
  *  graph variant shortcuts (e.g. `Graph::new_digraph()`)
- *  remove vertices/edges (assert strong count == 0)
  *  graph ops
  *      depth-first-search, breadth-first-search
  *  look up vertex / edge by either &'a ref or by &V/(&V,&V) ?
@@ -29,15 +27,21 @@
  *
  */
 
+use std::ptr;
 use std::slice;
+use std::vec;
 use std::rc::Rc;
 use std::borrow::Borrow;
 use std::collections::{hash_map, HashMap};
 
 mod dir;    use dir::{DirT, Dir, Undir};
-mod edge;   use edge::{EdgeT, Edge}; pub use edge::UnweightedEdge;
+mod edge;   use edge::{EdgeT, Edge}; pub use edge::{UnweightedEdge, NumT, WeightT, CapacityT, CostT};
 mod vertex; use vertex::{NodeT, Vertex};
+mod path;   pub use path::{Path, Cycle};
 mod iter;
+mod algo;   pub use algo::{EdgeRef, ShortestPaths, NegativeCycle, AllPairs};
+            pub use algo::{MaxFlow, MinCostFlow, FlowError, Matching, Assignment, AssignmentError};
+            pub use algo::{Isomorphisms, MaximalCliques, ColorOrder, PageRankConfig, Dangling, Communities};
 
 #[cfg(test)] mod test;
 
@@ -107,10 +111,10 @@ impl<V: NodeT, E: EdgeT, D: DirT<V,E>> Graph<V,E,D> {
     }
 
     // iterators
-    fn map_vals(&self) -> hash_map::Values<Rc<V>, Vertex<V,E,D>> {
+    fn map_vals(&self) -> hash_map::Values<'_, Rc<V>, Vertex<V,E,D>> {
         self.nodes.values()
     }
-    pub fn vertices(&self) -> iter::Vertices<V,E,D> {
+    pub fn vertices(&self) -> iter::Vertices<'_,V,E,D> {
         iter::Vertices::new(self)
     }
     pub fn get_reachable<'a>(&'a self, vert: &'a Vertex<V,E,D>) 
@@ -119,23 +123,126 @@ impl<V: NodeT, E: EdgeT, D: DirT<V,E>> Graph<V,E,D> {
         let reachable = vert.get_reachable().iter();
         iter::Neighbors::reachable(self, reachable)
     }
-    pub fn edges(&self) -> slice::Iter<Rc<E>> {
+    /// Every vertex sharing an edge with `vert`, whichever way it points
+    pub(crate) fn get_adjacent<'a>(&'a self, vert: &'a Vertex<V,E,D>) 
+        -> iter::Neighbors<'a,V,E,D>
+    {
+        let backward = vert.get_backward().iter();
+        let reachable = vert.get_reachable().iter();
+        iter::Neighbors::adjacent(self, backward, reachable)
+    }
+    pub fn edges(&self) -> slice::Iter<'_, Rc<E>> {
         // should this be a different Item? e.g. just a &'a (&V,&V)?
         self.edges.iter()
     }
     pub fn breadth_first<'a>(&'a self, start: Option<&'a Vertex<V,E,D>>) 
-        -> iter::BreadthFirst<V,E,D> 
+        -> iter::BreadthFirst<'a,V,E,D> 
     {
         iter::BreadthFirst::new(self, start)
     }
     pub fn depth_first<'a>(&'a self, start: Option<&'a Vertex<V,E,D>>)
-        -> iter::DepthFirst<V,E,D>
+        -> iter::DepthFirst<'a,V,E,D>
     {
         iter::DepthFirst::new(self, start)
     }
-    pub fn components(&self) -> iter::Components<V,E,D> {
+    /// Sets of vertices reachable from one another; for a `DiGraph` this
+    ///  depends on iteration order, see `weak_components` and
+    ///  `strong_components` instead
+    pub fn components(&self) -> iter::Components<'_,V,E,D> {
         iter::Components::new(self)
     }
+    /// Whether the vertices can be split in two with no edge inside either;
+    ///  edges join the same pairs whichever way they point, so direction is
+    ///  ignored
+    pub fn is_bipartite(&self) -> bool {
+        self.bipartition().is_ok()
+    }
+    /// Split the vertices in two with no edge inside either half, or find an
+    ///  odd cycle proving that's impossible (which in a `DiGraph` may follow
+    ///  edges backwards)
+    pub fn bipartition(&self) 
+        -> Result<(Vec<&Vertex<V,E,D>>, Vec<&Vertex<V,E,D>>), Cycle<'_,V,E,D>>
+    {
+        algo::bipartite::bipartition(self)
+    }
+
+    // search
+    /// Cheapest path from `start` to `goal` and its cost, guided by a
+    ///  `heuristic` that never overestimates the cost remaining to `goal`
+    pub fn astar<'a, W, H, C>(&'a self, 
+                              start: &'a Vertex<V,E,D>, 
+                              goal: &'a Vertex<V,E,D>,
+                              heuristic: H,
+                              cost: C)
+        -> Option<(Path<'a,V,E,D>, W)>
+        where W: NumT, H: Fn(&V) -> W, C: Fn(&E) -> W
+    {
+        algo::shortest_path::astar(self, start, goal, heuristic, cost)
+    }
+
+    /// A path through every vertex exactly once, found by backtracking
+    pub fn hamiltonian_path(&self) -> Option<Path<'_,V,E,D>> {
+        algo::tour::hamiltonian(self, false)
+    }
+    /// A cycle through every vertex exactly once, found by backtracking
+    pub fn hamiltonian_cycle(&self) -> Option<Cycle<'_,V,E,D>> {
+        algo::tour::hamiltonian(self, true).map(Cycle::new)
+    }
+    /// Whether `other` is this graph with its vertices relabeled
+    pub fn is_isomorphic(&self, other: &Self) -> bool {
+        self.is_isomorphic_by(other, algo::isomorphism::any, algo::isomorphism::any)
+    }
+    /// As `is_isomorphic`, but only pairing up vertices and edges that are
+    ///  equivalent by `node_eq` and `edge_eq` (which get this graph's first)
+    pub fn is_isomorphic_by<FV, FE>(&self, other: &Self, node_eq: FV, edge_eq: FE) -> bool
+        where FV: Fn(&V, &V) -> bool, FE: Fn(&E, &E) -> bool
+    {
+        Isomorphisms::new(self, other, true, node_eq, edge_eq).next().is_some()
+    }
+    /// Every way `pattern` appears as an induced subgraph of this one, as a 
+    ///  map from each of its vertices to one of this graph's
+    pub fn subgraph_isomorphisms<'a>(&'a self, pattern: &'a Self) 
+        -> Isomorphisms<'a, V, E, D, fn(&V, &V) -> bool, fn(&E, &E) -> bool>
+    {
+        Isomorphisms::new(self, pattern, false, algo::isomorphism::any, algo::isomorphism::any)
+    }
+    /// As `subgraph_isomorphisms`, but only pairing up vertices and edges 
+    ///  that are equivalent by `node_eq` and `edge_eq` (which get this graph's first)
+    pub fn subgraph_isomorphisms_by<'a, FV, FE>(&'a self, pattern: &'a Self, node_eq: FV, edge_eq: FE)
+        -> Isomorphisms<'a, V, E, D, FV, FE>
+        where FV: Fn(&V, &V) -> bool, FE: Fn(&E, &E) -> bool
+    {
+        Isomorphisms::new(self, pattern, false, node_eq, edge_eq)
+    }
+    /// How many shortest paths between other vertices go through each one, 
+    ///  split evenly among ties (Brandes)
+    pub fn betweenness_centrality(&self) -> HashMap<&V, f64> {
+        algo::centrality::brandes(self, |v| algo::shortest_path::settle_breadth_first(self, v)).0
+    }
+    /// How many shortest paths between vertices go along each edge, split 
+    ///  evenly among ties
+    pub fn edge_betweenness_centrality(&self) -> Vec<(EdgeRef<'_,V,E,D>, f64)> {
+        algo::centrality::brandes(self, |v| algo::shortest_path::settle_breadth_first(self, v)).1
+    }
+    /// Inverse of the average distance from each vertex to those it reaches, 
+    ///  scaled by how many it reaches
+    pub fn closeness_centrality(&self) -> HashMap<&V, f64> {
+        algo::centrality::closeness(self, |v| algo::shortest_path::settle_breadth_first(self, v))
+    }
+    /// Sum of the inverse distances from each vertex to every other
+    pub fn harmonic_centrality(&self) -> HashMap<&V, f64> {
+        algo::centrality::harmonic(self, |v| algo::shortest_path::settle_breadth_first(self, v))
+    }
+    /// Scores proportional to the sum of those of the vertices with edges to 
+    ///  each, scaled so their squares sum to one
+    pub fn eigenvector_centrality(&self) -> HashMap<&V, f64> {
+        algo::centrality::eigenvector(self, |_| 1.0)
+    }
+    /// Each score is one plus `alpha` times the sum of those of the vertices 
+    ///  with edges to it; `None` if `alpha` is too large for that to settle
+    pub fn katz_centrality(&self, alpha: f64) -> Option<HashMap<&V, f64>> {
+        algo::centrality::katz(self, alpha, |_| 1.0)
+    }
 
     // modifiers
     pub fn insert_vertex(&mut self, v: V) -> Option<Rc<V>> {
@@ -160,6 +267,128 @@ impl<V: NodeT, E: EdgeT, D: DirT<V,E>> Graph<V,E,D> {
 
         self.edges.last().map(|e| e.as_ref())
     }
+    /// Remove a vertex and every edge incident to it
+    ///
+    /// Returns `None` (and leaves the graph untouched) if `v` isn't present or
+    ///  if an `Rc<V>` handed out by `insert_vertex` is still alive
+    pub fn remove_vertex(&mut self, v: &V) -> Option<V> {
+        if self.get_vertex(v)?.is_shared() { return None }
+        let (key, mut vert) = self.nodes.remove_entry(v)?;
+        drop(key);
+        let unlinked = vert.unregister_all();
+        for edge in &unlinked {
+            if let Some(other) = self.nodes.get_mut(edge.get_end()) {
+                other.unregister(edge.as_ref());
+            }
+        }
+        self.edges.retain(|e| unlinked.iter().any(|u| ptr::eq(u.as_ref(), e.as_ref())) == false);
+        drop(unlinked);
+        vert.into_inner()
+    }
+    /// Remove one edge from `l` to `r` (for undirected graphs, between them)
+    ///
+    /// Returns `None` (and leaves the graph untouched) if there is no such edge
+    ///  or if an `Rc<E>` obtained from `edges()` is still alive
+    pub fn remove_edge(&mut self, l: &V, r: &V) -> Option<E> {
+        let edge = self.get_vertex(l)?.get_reachable().iter()
+            .find(|e| e.get_end() == r)?
+            .get_val();
+        if self.edge_is_shared(&edge) { return None }
+        Some(self.unlink_edge(edge, l, r))
+    }
+    /// Remove every edge between `l` and `r` in either direction
+    ///
+    /// Returns `None` (and leaves the graph untouched) if either vertex is
+    ///  missing or if any of the edges is still referenced outside the graph
+    pub fn remove_edges_between(&mut self, l: &V, r: &V) -> Option<Vec<E>> {
+        let mut found: Vec<(Rc<E>, &V, &V)> = vec![];
+        for &(src, dst) in &[(l, r), (r, l)] {
+            for e in self.get_vertex(src)?.get_reachable().iter().filter(|e| e.get_end() == dst) {
+                if found.iter().all(|(f, _, _)| ptr::eq(f.as_ref(), e.as_ref()) == false) {
+                    found.push((e.get_val(), src, dst));
+                }
+            }
+        }
+        if found.iter().any(|(e, _, _)| self.edge_is_shared(e)) { return None }
+        Some(found.into_iter().map(|(e, src, dst)| self.unlink_edge(e, src, dst)).collect())
+    }
+
+    fn edge_is_shared(&self, edge: &Rc<E>) -> bool {
+        // one per endpoint, one in `edges`, and the caller's
+        Rc::strong_count(edge) > 4
+    }
+    fn unlink_edge(&mut self, edge: Rc<E>, l: &V, r: &V) -> E {
+        for v in &[l, r] {
+            let removed = self.nodes.get_mut(*v).and_then(|v| v.unregister(&edge));
+            debug_assert!(removed.is_some());
+        }
+        let i = self.edges.iter().position(|e| Rc::ptr_eq(e, &edge));
+        self.edges.remove(i.expect("edge missing from `Graph::edges`"));
+        Rc::try_unwrap(edge).expect("edge is still shared")
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Weighted Graphs
+///////////////////////////////////////////////////////////////////////////////
+
+impl<V: NodeT, E: WeightT, D: DirT<V,E>> Graph<V,E,D> {
+    /// Single-source shortest paths; edge weights must be non-negative
+    pub fn dijkstra<'a>(&'a self, start: &'a Vertex<V,E,D>) 
+        -> ShortestPaths<'a,V,E,D,E::W>
+    {
+        algo::shortest_path::dijkstra(self, start)
+    }
+    /// All-pairs shortest paths in O(n³); suited to dense graphs
+    pub fn floyd_warshall(&self) 
+        -> Result<AllPairs<'_,V,E,D,E::W>, NegativeCycle<'_,V,E,D>>
+    {
+        algo::all_pairs::floyd_warshall(self)
+    }
+    /// All-pairs shortest paths in O(nm log n); suited to sparse graphs
+    pub fn johnson(&self) 
+        -> Result<AllPairs<'_,V,E,D,E::W>, NegativeCycle<'_,V,E,D>>
+    {
+        algo::all_pairs::johnson(self)
+    }
+    /// As `betweenness_centrality` along the lightest paths; weights must be
+    ///  positive
+    pub fn weighted_betweenness_centrality(&self) -> HashMap<&V, f64> {
+        let settle = |v| algo::shortest_path::settle_by(self, v, |_, e, _| e.weight());
+        algo::centrality::brandes(self, settle).0
+    }
+    /// As `edge_betweenness_centrality` along the lightest paths; weights 
+    ///  must be positive
+    pub fn weighted_edge_betweenness_centrality(&self) -> Vec<(EdgeRef<'_,V,E,D>, f64)> {
+        let settle = |v| algo::shortest_path::settle_by(self, v, |_, e, _| e.weight());
+        algo::centrality::brandes(self, settle).1
+    }
+    /// As `closeness_centrality` with distances measured by weight
+    pub fn weighted_closeness_centrality(&self) -> HashMap<&V, f64> {
+        let settle = |v| algo::shortest_path::settle_by(self, v, |_, e, _| e.weight());
+        algo::centrality::closeness(self, settle)
+    }
+    /// As `harmonic_centrality` with distances measured by weight; weights 
+    ///  must be positive
+    pub fn weighted_harmonic_centrality(&self) -> HashMap<&V, f64> {
+        let settle = |v| algo::shortest_path::settle_by(self, v, |_, e, _| e.weight());
+        algo::centrality::harmonic(self, settle)
+    }
+    /// As `eigenvector_centrality` with each edge counting for its weight
+    pub fn weighted_eigenvector_centrality(&self) -> HashMap<&V, f64> {
+        algo::centrality::eigenvector(self, |e| e.weight().to_f64())
+    }
+    /// As `katz_centrality` with each edge counting for its weight
+    pub fn weighted_katz_centrality(&self, alpha: f64) -> Option<HashMap<&V, f64>> {
+        algo::centrality::katz(self, alpha, |e| e.weight().to_f64())
+    }
+    /// Lightest cycle through every vertex exactly once (the travelling
+    ///  salesman's tour); exponential in time and memory, so `None` for more
+    ///  than 20 vertices as well as when there's no such cycle. `two_opt`
+    ///  and `christofides` approximate it for bigger undirected graphs
+    pub fn held_karp(&self) -> Option<Cycle<'_,V,E,D>> {
+        algo::tour::held_karp(self)
+    }
 }
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -179,6 +408,173 @@ impl<V: NodeT, E: EdgeT> Graph<V, E, Undir<V,E>> {
         let neighbors = vert.get_neighbor_edges().iter();
         iter::Neighbors::undir_neighbors(self, neighbors)
     }
+    /// Maximum-cardinality matching of a bipartite graph and a minimum vertex
+    ///  cover of the same size, or an odd cycle if the graph isn't bipartite
+    pub fn bipartite_matching(&self) 
+        -> Result<(Matching<'_,V,E>, Vec<&Vertex<V, E, Undir<V,E>>>), Cycle<'_, V, E, Undir<V,E>>>
+    {
+        algo::matching::hopcroft_karp(self)
+    }
+    /// As `bipartite_matching` with the sides given by `is_left`; edges 
+    ///  within one side are ignored
+    pub fn bipartite_matching_by<F>(&self, is_left: F) 
+        -> (Matching<'_,V,E>, Vec<&Vertex<V, E, Undir<V,E>>>)
+        where F: Fn(&V) -> bool
+    {
+        algo::matching::hopcroft_karp_by(self, is_left)
+    }
+    /// Maximum-cardinality matching of any graph, by Edmonds' blossom algorithm
+    pub fn max_cardinality_matching(&self) -> Matching<'_,V,E> {
+        algo::matching::max_cardinality_matching(self)
+    }
+    /// A closed walk through every edge exactly once, if every vertex has 
+    ///  even degree and the edges are connected
+    pub fn eulerian_circuit(&self) -> Option<Cycle<'_, V, E, Undir<V,E>>> {
+        algo::euler::eulerian_circuit(self, algo::euler::undirected_ends(self))
+    }
+    /// A walk through every edge exactly once, between the two vertices of 
+    ///  odd degree if there are any
+    pub fn eulerian_path(&self) -> Option<Path<'_, V, E, Undir<V,E>>> {
+        algo::euler::eulerian_path(self, algo::euler::undirected_ends(self))
+    }
+    /// Every complete subgraph that can't be grown by another vertex, each as
+    ///  its set of vertices
+    pub fn maximal_cliques(&self) -> MaximalCliques<'_,V,E> {
+        MaximalCliques::new(self)
+    }
+    /// A largest complete subgraph; exponential in the worst case
+    pub fn maximum_clique(&self) -> Vec<&Vertex<V, E, Undir<V,E>>> {
+        algo::clique::maximum_clique(self)
+    }
+    /// Colors `0, 1, ..` such that no two neighbors share one, given greedily 
+    ///  in the given order; self-loops are ignored
+    pub fn greedy_coloring(&self, order: ColorOrder) -> HashMap<&V, usize> {
+        algo::coloring::greedy(self, order)
+    }
+    /// Greedy coloring that next colors whichever vertex has neighbors of the
+    ///  most distinct colors; often fewer colors than a fixed order
+    pub fn dsatur_coloring(&self) -> HashMap<&V, usize> {
+        algo::coloring::dsatur(self)
+    }
+    /// Coloring with the fewest colors possible; exponential, so only for 
+    ///  small graphs
+    pub fn exact_coloring(&self) -> HashMap<&V, usize> {
+        algo::coloring::exact(self)
+    }
+    /// Fewest colors needed so no two neighbors share one
+    pub fn chromatic_number(&self) -> usize {
+        self.exact_coloring().values().max().map_or(0, |&c| c + 1)
+    }
+    /// Number of triangles through each vertex, ignoring self-loops and 
+    ///  counting parallel edges once
+    pub fn triangles(&self) -> HashMap<&V, usize> {
+        algo::triangles::triangles(self)
+    }
+    /// Number of triangles in the graph
+    pub fn triangle_count(&self) -> usize {
+        algo::triangles::triangle_count(self)
+    }
+    /// Fraction of each vertex's pairs of neighbors that are adjacent
+    pub fn clustering_coefficient(&self) -> HashMap<&V, f64> {
+        algo::triangles::clustering(self)
+    }
+    /// Mean clustering coefficient over every vertex
+    pub fn average_clustering(&self) -> f64 {
+        let local = self.clustering_coefficient();
+        if local.is_empty() { 0.0 } else { local.values().sum::<f64>() / local.len() as f64 }
+    }
+    /// Fraction of paths of length two that are closed into triangles
+    pub fn transitivity(&self) -> f64 {
+        algo::triangles::transitivity(self)
+    }
+    /// Edges whose removal would disconnect their endpoints
+    pub fn bridges(&self) -> Vec<EdgeRef<'_, V, E, Undir<V,E>>> {
+        algo::connectivity::low_link(self).bridges
+    }
+    /// Vertices whose removal would split their component (cut vertices)
+    pub fn articulation_points(&self) -> Vec<&Vertex<V, E, Undir<V,E>>> {
+        algo::connectivity::low_link(self).cut_vertices
+    }
+    /// Maximal subgraphs without a cut vertex, each as a set of edges
+    pub fn biconnected_components(&self) 
+        -> vec::IntoIter<Vec<EdgeRef<'_, V, E, Undir<V,E>>>>
+    {
+        algo::connectivity::low_link(self).blocks.into_iter()
+    }
+}
+
+impl<V: NodeT, E: EdgeT + Ord> Graph<V, E, Undir<V,E>> {
+    /// Minimum spanning forest, built by adding edges cheapest first
+    pub fn kruskal(&self) -> UndirectedGraph<&V, &E> {
+        algo::spanning_tree::kruskal(self)
+    }
+    /// Minimum spanning forest, built by growing one tree at a time
+    pub fn prim(&self) -> UndirectedGraph<&V, &E> {
+        algo::spanning_tree::prim(self)
+    }
+    /// Minimum spanning forest, built by merging every tree at once
+    pub fn boruvka(&self) -> UndirectedGraph<&V, &E> {
+        algo::spanning_tree::boruvka(self)
+    }
+}
+
+impl<V: NodeT, E: WeightT> Graph<V, E, Undir<V,E>> {
+    /// Matching of greatest total weight in any graph, by Edmonds' blossom
+    ///  algorithm; edges without positive weight are never used
+    pub fn max_weight_matching(&self) -> Matching<'_,V,E> {
+        algo::matching::max_weight_matching(self)
+    }
+    /// A short closed walk through every vertex, found by uncrossing legs of 
+    ///  the nearest-neighbor tour (2-opt). Stops are joined by shortest paths,
+    ///  so vertices can repeat; `None` if the graph is disconnected. Weights
+    ///  can't be negative
+    pub fn two_opt(&self) -> Option<Path<'_, V, E, Undir<V,E>>> {
+        algo::tour::two_opt(self)
+    }
+    /// As `two_opt`, but by Christofides' algorithm; at most 1.5 times the 
+    ///  length of the shortest such walk
+    pub fn christofides(&self) -> Option<Path<'_, V, E, Undir<V,E>>> {
+        algo::tour::christofides(self)
+    }
+    /// Perfect matching between the two sides of a bipartite graph with the
+    ///  least total weight, by the Hungarian algorithm
+    pub fn min_cost_assignment(&self) 
+        -> Result<Assignment<'_, V, E, E::W>, AssignmentError<'_,V,E>>
+    {
+        algo::assignment::hungarian(self, false)
+    }
+    /// As `min_cost_assignment`, but with the greatest total weight
+    pub fn max_weight_assignment(&self) 
+        -> Result<Assignment<'_, V, E, E::W>, AssignmentError<'_,V,E>>
+    {
+        algo::assignment::hungarian(self, true)
+    }
+    /// As `min_cost_assignment` with the sides given by `is_left`; edges 
+    ///  within one side are ignored
+    pub fn min_cost_assignment_by<F>(&self, is_left: F) 
+        -> Result<Assignment<'_, V, E, E::W>, AssignmentError<'_,V,E>>
+        where F: Fn(&V) -> bool
+    {
+        algo::assignment::hungarian_by(self, is_left, false)
+    }
+    /// As `max_weight_assignment` with the sides given by `is_left`; edges 
+    ///  within one side are ignored
+    pub fn max_weight_assignment_by<F>(&self, is_left: F) 
+        -> Result<Assignment<'_, V, E, E::W>, AssignmentError<'_,V,E>>
+        where F: Fn(&V) -> bool
+    {
+        algo::assignment::hungarian_by(self, is_left, true)
+    }
+    /// Communities found by Louvain's greedy modularity optimization; edge 
+    ///  weights should be positive
+    pub fn louvain(&self) -> Communities<'_,V,E> {
+        algo::community::louvain(self)
+    }
+    /// Communities found by asynchronous label propagation, which is faster 
+    ///  than Louvain but depends on the random order given by `seed`
+    pub fn label_propagation(&self, seed: u64) -> Communities<'_,V,E> {
+        algo::community::label_propagation(self, seed)
+    }
 }
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -211,6 +607,125 @@ impl<V: NodeT, E: EdgeT> Graph<V, E, Dir<V,E>> {
         let children = vert.get_child_edges().iter();
         iter::Neighbors::children(self, children)
     }
+    /// Lazily yield vertices such that every edge points forward
+    pub fn topological(&self) -> iter::Topological<'_,V,E> {
+        iter::Topological::new(self)
+    }
+    /// Order the vertices so every edge points forward, or find a cycle
+    pub fn topological_sort(&self) 
+        -> Result<Vec<&Vertex<V, E, Dir<V,E>>>, Cycle<'_, V, E, Dir<V,E>>>
+    {
+        let mut topo = self.topological();
+        let order: Vec<_> = topo.by_ref().collect();
+        match topo.find_cycle() {
+            Some(cycle) => Err(cycle),
+            None => Ok(order),
+        }
+    }
+    /// Components of the underlying undirected graph
+    pub fn weak_components(&self) -> iter::WeakComponents<'_,V,E> {
+        iter::WeakComponents::new(self)
+    }
+    /// Strongly connected components in reverse topological order
+    pub fn strong_components(&self) -> iter::StrongComponents<'_,V,E> {
+        iter::StrongComponents::new(self)
+    }
+    /// A closed walk through every edge exactly once, if every vertex has as
+    ///  many edges in as out and the edges are connected
+    pub fn eulerian_circuit(&self) -> Option<Cycle<'_, V, E, Dir<V,E>>> {
+        algo::euler::eulerian_circuit(self, algo::euler::directed_ends(self))
+    }
+    /// A walk through every edge exactly once; if it isn't closed it starts 
+    ///  at the vertex with an extra edge out and ends at the one with an extra in
+    pub fn eulerian_path(&self) -> Option<Path<'_, V, E, Dir<V,E>>> {
+        algo::euler::eulerian_path(self, algo::euler::directed_ends(self))
+    }
+    /// How likely a random surfer is to be at each vertex, following edges or 
+    ///  teleporting anywhere; the ranks sum to one
+    pub fn page_rank(&self, config: &PageRankConfig) -> HashMap<&V, f64> {
+        algo::pagerank::page_rank(self, &[], |_| 1.0, config)
+    }
+    /// As `page_rank`, but teleporting only to `seeds`, so ranks measure 
+    ///  closeness to them
+    pub fn personalized_page_rank(&self, seeds: &[&Vertex<V, E, Dir<V,E>>], config: &PageRankConfig)
+        -> HashMap<&V, f64>
+    {
+        algo::pagerank::page_rank(self, seeds, |_| 1.0, config)
+    }
+    /// Hub and authority scores: good hubs have edges to good authorities, 
+    ///  which have edges from good hubs. Each sums to one
+    pub fn hits(&self) -> (HashMap<&V, f64>, HashMap<&V, f64>) {
+        algo::centrality::hits(self, |_| 1.0)
+    }
+    /// The DAG formed by contracting each strong component to one vertex
+    pub fn condensation(&self) -> DiGraph<Vec<&V>, UnweightedEdge> {
+        algo::connectivity::condensation(self)
+    }
+}
+
+impl<V: NodeT, E: WeightT> Graph<V, E, Dir<V,E>> {
+    /// Single-source shortest paths allowing negative weights
+    pub fn bellman_ford<'a>(&'a self, start: &'a Vertex<V, E, Dir<V,E>>)
+        -> Result<ShortestPaths<'a, V, E, Dir<V,E>, E::W>, NegativeCycle<'a, V, E, Dir<V,E>>>
+    {
+        algo::shortest_path::bellman_ford(self, start)
+    }
+    /// As `page_rank`, but following edges in proportion to their weight
+    pub fn weighted_page_rank(&self, config: &PageRankConfig) -> HashMap<&V, f64> {
+        algo::pagerank::page_rank(self, &[], |e| e.weight().to_f64(), config)
+    }
+    /// As `personalized_page_rank`, but following edges in proportion to 
+    ///  their weight
+    pub fn weighted_personalized_page_rank(&self, 
+                                           seeds: &[&Vertex<V, E, Dir<V,E>>], 
+                                           config: &PageRankConfig)
+        -> HashMap<&V, f64>
+    {
+        algo::pagerank::page_rank(self, seeds, |e| e.weight().to_f64(), config)
+    }
+    /// As `hits` with each edge counting for its weight
+    pub fn weighted_hits(&self) -> (HashMap<&V, f64>, HashMap<&V, f64>) {
+        algo::centrality::hits(self, |e| e.weight().to_f64())
+    }
+}
+
+impl<V: NodeT, E: CapacityT> Graph<V, E, Dir<V,E>> {
+    /// Maximum flow from `source` to `sink` by shortest augmenting paths
+    pub fn edmonds_karp<'a>(&'a self, 
+                            source: &'a Vertex<V, E, Dir<V,E>>, 
+                            sink: &'a Vertex<V, E, Dir<V,E>>)
+        -> MaxFlow<'a, V, E, E::W>
+    {
+        algo::flow::edmonds_karp(self, source, sink)
+    }
+    /// Maximum flow from `source` to `sink` by blocking flows
+    pub fn dinic<'a>(&'a self, 
+                     source: &'a Vertex<V, E, Dir<V,E>>, 
+                     sink: &'a Vertex<V, E, Dir<V,E>>)
+        -> MaxFlow<'a, V, E, E::W>
+    {
+        algo::flow::dinic(self, source, sink)
+    }
+}
+
+impl<V: NodeT, E: CostT> Graph<V, E, Dir<V,E>> {
+    /// Maximum flow from `source` to `sink` of the least total cost, which
+    ///  may also send flow around cycles of negative cost
+    pub fn min_cost_max_flow<'a>(&'a self, 
+                                 source: &'a Vertex<V, E, Dir<V,E>>, 
+                                 sink: &'a Vertex<V, E, Dir<V,E>>)
+        -> MinCostFlow<'a, V, E, E::W>
+    {
+        algo::flow::min_cost_max_flow(self, source, sink)
+    }
+    /// Cheapest flow in which every vertex `v` receives `demand(v)` more 
+    ///  than it sends (so a negative demand is a supply)
+    pub fn min_cost_flow<F>(&self, demand: F) 
+        -> Result<MinCostFlow<'_, V, E, E::W>, FlowError<E::W>>
+        where F: Fn(&V) -> E::W
+    {
+        algo::flow::min_cost_flow(self, demand)
+    }
 }
 
 
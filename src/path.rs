@@ -0,0 +1,113 @@
+
+use std::slice;
+
+use dir::DirT;
+use edge::{EdgeT, WeightT, NumT};
+use vertex::{NodeT, Vertex};
+
+///////////////////////////////////////////////////////////////////////////////
+//  Path
+///////////////////////////////////////////////////////////////////////////////
+
+/// A walk through a graph: `n+1` vertices joined by `n` edges
+#[derive(Debug)]
+pub struct Path<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>> {
+    verts: Vec<&'a Vertex<V,E,D>>,
+    edges: Vec<&'a E>,
+}
+
+impl<'a, V: NodeT, E: EdgeT, D: DirT<V,E>> Clone for Path<'a,V,E,D> {
+    fn clone(&self) -> Self {
+        Path { verts: self.verts.clone(), edges: self.edges.clone() }
+    }
+}
+
+impl<'a, V: NodeT, E: EdgeT, D: DirT<V,E>> Path<'a,V,E,D> {
+    pub(crate) fn new(start: &'a Vertex<V,E,D>) -> Self {
+        Path { verts: vec![start], edges: vec![] }
+    }
+    pub(crate) fn push(&mut self, edge: &'a E, next: &'a Vertex<V,E,D>) {
+        self.edges.push(edge);
+        self.verts.push(next);
+    }
+    /// Build a path out of steps collected while walking back from its end
+    pub(crate) fn from_reversed(end: &'a Vertex<V,E,D>,
+                                mut steps: Vec<(&'a Vertex<V,E,D>, &'a E)>)
+        -> Self
+    {
+        steps.reverse();
+        let mut verts = Vec::with_capacity(steps.len() + 1);
+        let mut edges = Vec::with_capacity(steps.len());
+        for (v, e) in steps {
+            verts.push(v);
+            edges.push(e);
+        }
+        verts.push(end);
+        Path { verts, edges }
+    }
+
+    pub fn start(&self) -> &'a Vertex<V,E,D> {
+        self.verts[0]
+    }
+    pub fn end(&self) -> &'a Vertex<V,E,D> {
+        self.verts[self.verts.len() - 1]
+    }
+    /// Number of edges in the path
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+    /// Whether the path is just a single vertex
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+    pub fn vertices(&self) -> slice::Iter<'_, &'a Vertex<V,E,D>> {
+        self.verts.iter()
+    }
+    pub fn edges(&self) -> slice::Iter<'_, &'a E> {
+        self.edges.iter()
+    }
+    /// Each edge along with the vertices on either side of it
+    pub fn steps<'b>(&'b self)
+        -> impl Iterator<Item=(&'a Vertex<V,E,D>, &'a E, &'a Vertex<V,E,D>)> + 'b
+    {
+        self.verts.windows(2).zip(self.edges.iter())
+            .map(|(w, &e)| (w[0], e, w[1]))
+    }
+}
+
+impl<'a, V: NodeT, E: WeightT, D: DirT<V,E>> Path<'a,V,E,D> {
+    /// Sum of the weights of every edge in the path
+    pub fn weight(&self) -> E::W {
+        self.edges.iter().fold(E::W::zero(), |acc, e| acc + e.weight())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  Cycle
+///////////////////////////////////////////////////////////////////////////////
+
+/// A closed path: its first and last vertices are the same
+#[derive(Debug)]
+pub struct Cycle<'a, V: 'a+NodeT, E: 'a+EdgeT, D: 'a+DirT<V,E>> {
+    path: Path<'a,V,E,D>,
+}
+
+impl<'a, V: NodeT, E: EdgeT, D: DirT<V,E>> Cycle<'a,V,E,D> {
+    pub(crate) fn new(path: Path<'a,V,E,D>) -> Self {
+        debug_assert!(path.start().get() == path.end().get(), "path isn't closed");
+        Cycle { path }
+    }
+    pub fn path(&self) -> &Path<'a,V,E,D> {
+        &self.path
+    }
+    pub fn into_path(self) -> Path<'a,V,E,D> {
+        self.path
+    }
+    /// Number of edges (and distinct vertices) in the cycle
+    pub fn len(&self) -> usize {
+        self.path.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.path.is_empty()
+    }
+}
@@ -1,5 +1,5 @@
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::borrow::Borrow;
 
 use super::{DiGraph, Graph, UnweightedUndirectedGraph, UndirectedGraph};
@@ -64,8 +64,8 @@ fn neighborhood_sizes() {
     for i in g.vertices() {
         let degree = match *i.borrow() {
             0       => 9,       // edge to 0..9
-            1...9   => 1 + 10,  // edge to 0 and n0..n9
-            10...99 => 1,       // edge to range(00,90,10)
+            1..=9   => 1 + 10,  // edge to 0 and n0..n9
+            10..=99 => 1,       // edge to range(00,90,10)
             _ => unreachable!(),
         };
         assert_eq!(i.degree(), degree, "Failed at node {:?}", i);
@@ -106,8 +106,8 @@ fn breadth_first_lattice_undir() {
         let x: usize = *n.get();
         match i { 
             0       => assert_eq!(x, 0, "started at wrong node"),
-            1 ... 9 => { assert!(x > 0); assert!(x < 10) },
-            10...99 => { assert!(x > 9); assert!(x < 100) },
+            1..=9 => { assert!(x > 0); assert!(x < 10) },
+            10..=99 => { assert!(x > 9); assert!(x < 100) },
             _ => unreachable!(),
         }
     }
@@ -164,6 +164,1794 @@ fn bfs_germany_undir() {
     assert!(cities.next().is_none());
 }
 
+#[test]
+fn remove_vertex_germany() {
+    let mut g = germany_wiki_map();
+    assert_eq!(Some("Würzburg"), g.remove_vertex(&"Würzburg"));
+    assert_eq!(9, g.order());
+    assert_eq!(8, g.size());
+    assert!(g.get_vertex(&"Würzburg").is_none());
+    assert!(g.are_adjacent(&"Nürnberg", &"Würzburg") == false);
+    assert!(g.vertices().all(|v| v.edge_to(&"Würzburg").is_none()));
+    assert_eq!(2, g.get_vertex(&"Frankfurt").unwrap().degree());
+    // Erfurt only bordered Würzburg
+    assert_eq!(0, g.get_vertex(&"Erfurt").unwrap().degree());
+    assert_eq!(None, g.remove_vertex(&"Würzburg"));
+}
+
+#[test]
+fn remove_edges_dir() {
+    let mut g = DiGraph::<char, u8>::new();
+    g.insert_vertex('A');
+    g.insert_vertex('B');
+    g.insert_directed_edge(1, &'A', &'B');
+    g.insert_directed_edge(2, &'B', &'A');
+    g.insert_directed_edge(3, &'A', &'B');
+    g.insert_directed_edge(4, &'A', &'A');
+    assert_eq!(Some(&1), g.get_vertex(&'A').unwrap().edge_to(&'B'));
+
+    assert_eq!(None, g.remove_edge(&'B', &'B'));
+    assert_eq!(Some(2), g.remove_edge(&'B', &'A'));
+    assert_eq!(None, g.remove_edge(&'B', &'A'));
+    assert_eq!(3, g.size());
+
+    let mut removed = g.remove_edges_between(&'A', &'B').unwrap();
+    removed.sort();
+    assert_eq!(vec![1, 3], removed);
+    assert_eq!(1, g.size());
+    assert_eq!(0, g.get_vertex(&'B').unwrap().degree());
+    // the loop moved to the front of A's edges and is still found
+    assert_eq!(None, g.get_vertex(&'A').unwrap().edge_to(&'B'));
+    assert_eq!(Some(&4), g.get_vertex(&'A').unwrap().edge_to(&'A'));
+
+    assert_eq!(Some(4), g.remove_edge(&'A', &'A'));
+    assert_eq!(0, g.get_vertex(&'A').unwrap().degree());
+    assert!(g.edges().next().is_none());
+}
+
+#[test]
+fn remove_shared() {
+    let mut g = UndirectedGraph::<char, u8>::new();
+    let a = g.insert_vertex('A').unwrap();
+    g.insert_vertex('B');
+    g.insert_undirected_edge(1, &'A', &'B');
+    g.insert_undirected_edge(2, &'B', &'B');
+
+    // outstanding `Rc`s keep the graph intact
+    assert_eq!(None, g.remove_vertex(&'A'));
+    let e = g.edges().next().unwrap().clone();
+    assert_eq!(None, g.remove_edge(&'B', &'A'));
+    assert_eq!(None, g.remove_edges_between(&'A', &'B'));
+    assert_eq!(2, g.size());
+
+    drop(e);
+    assert_eq!(Some(1), g.remove_edge(&'B', &'A'));
+    drop(a);
+    assert_eq!(Some('A'), g.remove_vertex(&'A'));
+    assert_eq!(Some('B'), g.remove_vertex(&'B'));
+    assert!(g.is_empty());
+    assert_eq!(0, g.size());
+}
+
+#[test]
+fn dijkstra_germany() {
+    let g = germany_wiki_map();
+    let sp = g.dijkstra(g.get_vertex(&"Frankfurt").unwrap());
+    let expected = [
+        ("Frankfurt", 0), ("Mannheim", 85), ("Karlsruhe", 165), ("Augsburg", 415),
+        ("Würzburg", 217), ("Nürnberg", 320), ("Erfurt", 403), ("Stuttgart", 503),
+        ("Kassel", 173), ("München", 487),
+    ];
+    for &(city, dist) in &expected {
+        assert_eq!(Some(dist), sp.distance(&city), "wrong distance to {}", city);
+    }
+    assert_eq!(g.order(), sp.distances().len());
+    assert!(sp.predecessor(&"Frankfurt").is_none());
+
+    let path = sp.path_to(&"München").unwrap();
+    let cities: Vec<&str> = path.vertices().map(|v| *v.get()).collect();
+    assert_eq!(vec!["Frankfurt", "Würzburg", "Nürnberg", "München"], cities);
+    assert_eq!(vec![217, 103, 167], path.edges().map(|&&e| e).collect::<Vec<_>>());
+    assert_eq!(3, path.len());
+    assert_eq!(487, path.weight());
+    assert!(path.steps().all(|(l, e, r)| l.edge_to(r.get()) == Some(e)));
+
+    let trivial = sp.path_to(&"Frankfurt").unwrap();
+    assert!(trivial.is_empty());
+    assert_eq!(&"Frankfurt", trivial.start().get());
+}
+
+#[test]
+fn dijkstra_dir_unreachable() {
+    let mut g = DiGraph::<char, u32>::new();
+    for c in "ABCD".chars() { g.insert_vertex(c); }
+    g.insert_directed_edge(7, &'A', &'B');
+    g.insert_directed_edge(1, &'A', &'C');
+    g.insert_directed_edge(2, &'C', &'B');
+    g.insert_directed_edge(1, &'D', &'A');
+    let sp = g.dijkstra(g.get_vertex(&'A').unwrap());
+    assert_eq!(Some(3), sp.distance(&'B'));
+    assert_eq!(&'C', sp.predecessor(&'B').unwrap().0.get());
+    assert_eq!(None, sp.distance(&'D'));
+    assert!(sp.path_to(&'D').is_none());
+}
+
+#[test]
+fn narrow_weights_dont_overflow() {
+    // lengths of `u8` and `i8` edges are summed in 64 bits
+    let mut g = DiGraph::<u8, u8>::new();
+    for i in 0..3 { g.insert_vertex(i); }
+    g.insert_directed_edge(200, &0, &1);
+    g.insert_directed_edge(100, &1, &2);
+    let sp = g.dijkstra(g.get_vertex(&0).unwrap());
+    assert_eq!(Some(300), sp.distance(&2));
+    assert_eq!(300, sp.path_to(&2).unwrap().weight());
+    assert_eq!(Some(300), g.floyd_warshall().unwrap().distance(&0, &2));
+
+    let mut g = DiGraph::<u8, i8>::new();
+    for i in 0..3 { g.insert_vertex(i); }
+    g.insert_directed_edge(-100, &0, &1);
+    g.insert_directed_edge(-100, &1, &2);
+    let sp = g.bellman_ford(g.get_vertex(&0).unwrap()).unwrap();
+    assert_eq!(Some(-200), sp.distance(&2));
+    assert_eq!(Some(-200), g.johnson().unwrap().distance(&0, &2));
+}
+
+#[test]
+fn bellman_ford_negative_edges() {
+    let mut g = DiGraph::<char, i32>::new();
+    for c in "STABC".chars() { g.insert_vertex(c); }
+    g.insert_directed_edge(4, &'S', &'A');
+    g.insert_directed_edge(5, &'S', &'B');
+    g.insert_directed_edge(-3, &'B', &'A');
+    g.insert_directed_edge(2, &'A', &'C');
+    g.insert_directed_edge(-1, &'C', &'T');
+    let sp = g.bellman_ford(g.get_vertex(&'S').unwrap()).unwrap();
+    assert_eq!(Some(2), sp.distance(&'A'));
+    assert_eq!(Some(3), sp.distance(&'T'));
+    let path = sp.path_to(&'T').unwrap();
+    let verts: String = path.vertices().map(|v| *v.get()).collect();
+    assert_eq!("SBACT", verts);
+    assert_eq!(3, path.weight());
+}
+
+#[test]
+fn bellman_ford_negative_cycle() {
+    let mut g = DiGraph::<char, i32>::new();
+    for c in "SABCD".chars() { g.insert_vertex(c); }
+    g.insert_directed_edge(1, &'S', &'A');
+    g.insert_directed_edge(1, &'A', &'B');
+    g.insert_directed_edge(-2, &'B', &'C');
+    g.insert_directed_edge(-1, &'C', &'A');
+    g.insert_directed_edge(1, &'C', &'D');
+    let err = g.bellman_ford(g.get_vertex(&'S').unwrap()).unwrap_err();
+    let cycle = err.path();
+    assert_eq!(3, cycle.len());
+    assert_eq!(-2, cycle.weight());
+    assert_eq!(cycle.start().get(), cycle.end().get());
+    let mut verts: Vec<char> = cycle.vertices().skip(1).map(|v| *v.get()).collect();
+    verts.sort();
+    assert_eq!(vec!['A', 'B', 'C'], verts);
+    assert!(cycle.steps().all(|(l, e, r)| l.edge_to(r.get()) == Some(e)));
+
+    // unreachable from D, so there's nothing wrong
+    let sp = g.bellman_ford(g.get_vertex(&'D').unwrap()).unwrap();
+    assert_eq!(1, sp.distances().len());
+}
+
+// `n`×`n` lattice with unit edges, minus the vertices in `walls`
+fn grid(n: i32, walls: &[(i32, i32)]) -> UndirectedGraph<(i32, i32), u32> {
+    let mut g = Graph::new();
+    for x in 0..n {
+        for y in 0..n {
+            if walls.contains(&(x, y)) == false {
+                g.insert_vertex((x, y));
+            }
+        }
+    }
+    for x in 0..n {
+        for y in 0..n {
+            if g.contains_key(&(x, y)) == false { continue }
+            for &(dx, dy) in &[(1, 0), (0, 1)] {
+                if g.contains_key(&(x+dx, y+dy)) {
+                    g.insert_undirected_edge(1, &(x, y), &(x+dx, y+dy));
+                }
+            }
+        }
+    }
+    g
+}
+
+#[test]
+fn astar_grid() {
+    // a wall down the middle with a gap at the bottom
+    let walls: Vec<(i32, i32)> = (0..9).map(|y| (5, y)).collect();
+    let g = grid(10, &walls);
+    let start = g.get_vertex(&(0, 0)).unwrap();
+    let goal = g.get_vertex(&(9, 0)).unwrap();
+    let manhattan = |&(x, y): &(i32, i32)| ((9 - x).abs() + y.abs()) as u32;
+    let (path, cost) = g.astar(start, goal, manhattan, |&e| e).unwrap();
+    assert_eq!(27, cost);
+    assert_eq!(27, path.len());
+    assert_eq!(&(0, 0), path.start().get());
+    assert_eq!(&(9, 0), path.end().get());
+    assert!(path.vertices().any(|v| v.get() == &(5, 9)));
+    assert_eq!(Some(cost), g.dijkstra(start).distance(&(9, 0)));
+
+    let walled = grid(3, &[(1, 0), (1, 1), (1, 2)]);
+    let (l, r) = (walled.get_vertex(&(0, 0)).unwrap(), walled.get_vertex(&(2, 2)).unwrap());
+    assert!(walled.astar(l, r, |_| 0, |&e| e).is_none());
+}
+
+#[test]
+fn astar_germany() {
+    let g = germany_wiki_map();
+    let start = g.get_vertex(&"Karlsruhe").unwrap();
+    let goal = g.get_vertex(&"Erfurt").unwrap();
+    let (path, cost) = g.astar(start, goal, |_| 0u32, |&e| u32::from(e)).unwrap();
+    let cities: Vec<&str> = path.vertices().map(|v| *v.get()).collect();
+    assert_eq!(vec!["Karlsruhe", "Mannheim", "Frankfurt", "Würzburg", "Erfurt"], cities);
+    assert_eq!(568, cost);
+}
+
+#[test]
+fn all_pairs_germany() {
+    let g = germany_wiki_map();
+    let fw = g.floyd_warshall().unwrap();
+    let jo = g.johnson().unwrap();
+    for src in g.vertices() {
+        let sp = g.dijkstra(src);
+        for dst in g.vertices() {
+            let (l, r) = (src.get(), dst.get());
+            assert_eq!(sp.distance(r), fw.distance(l, r));
+            assert_eq!(sp.distance(r), jo.distance(l, r));
+            for ap in &[&fw, &jo] {
+                let path = ap.path(l, r).unwrap();
+                assert_eq!(sp.distance(r), Some(path.weight()));
+                assert_eq!(path.vertices().nth(1).map(|v| v.get()), ap.next_hop(l, r).map(|v| v.get()));
+            }
+        }
+    }
+    assert_eq!("Würzburg", *fw.next_hop(&"Frankfurt", &"München").unwrap().get());
+    assert_eq!("Würzburg", *jo.next_hop(&"Frankfurt", &"München").unwrap().get());
+    assert!(fw.next_hop(&"Kassel", &"Kassel").is_none());
+}
+
+#[test]
+fn all_pairs_negative_dir() {
+    let mut g = DiGraph::<u8, i32>::new();
+    for i in 0..5 { g.insert_vertex(i); }
+    g.insert_directed_edge(3, &0, &1);
+    g.insert_directed_edge(8, &0, &2);
+    g.insert_directed_edge(-4, &0, &4);
+    g.insert_directed_edge(1, &1, &3);
+    g.insert_directed_edge(7, &1, &4);
+    g.insert_directed_edge(4, &2, &1);
+    g.insert_directed_edge(2, &3, &0);
+    g.insert_directed_edge(-5, &3, &2);
+    g.insert_directed_edge(6, &4, &3);
+    let fw = g.floyd_warshall().unwrap();
+    let jo = g.johnson().unwrap();
+    for l in 0..5 {
+        let sp = g.bellman_ford(g.get_vertex(&l).unwrap()).unwrap();
+        for r in 0..5 {
+            assert_eq!(sp.distance(&r), fw.distance(&l, &r));
+            assert_eq!(sp.distance(&r), jo.distance(&l, &r));
+            assert_eq!(sp.distance(&r), jo.path(&l, &r).map(|p| p.weight()));
+        }
+    }
+    assert_eq!(Some(-1), fw.distance(&1, &4));
+    assert_eq!(Some(-3), jo.distance(&0, &2));
+
+    g.insert_directed_edge(-9, &4, &0);
+    assert!(g.floyd_warshall().is_err());
+    let cycle = g.johnson().unwrap_err();
+    assert!(cycle.path().weight() < 0);
+}
+
+#[test]
+fn spanning_forest_germany() {
+    let g = germany_wiki_map();
+    for mst in [g.kruskal(), g.prim(), g.boruvka()] {
+        assert_eq!(g.order(), mst.order());
+        assert_eq!(g.order() - 1, mst.size());
+        assert_eq!(1, mst.components().count());
+        assert_eq!(1444, mst.edges().map(|e| u32::from(***e)).sum::<u32>());
+        assert!(mst.edge_between(&&"Frankfurt", &&"Würzburg").is_some());
+        assert!(mst.edge_between(&&"Kassel", &&"München").is_none());
+        // edges are borrowed from the original graph
+        let e = mst.edge_between(&&"Karlsruhe", &&"Mannheim").unwrap();
+        assert!(::std::ptr::eq(*e, g.edge_between(&"Karlsruhe", &"Mannheim").unwrap()));
+    }
+}
+
+#[test]
+fn spanning_forest_disconnected() {
+    let mut g = UndirectedGraph::<u8, u8>::new();
+    for i in 0..7 { g.insert_vertex(i); }
+    g.insert_undirected_edge(4, &0, &1);
+    g.insert_undirected_edge(1, &1, &2);
+    g.insert_undirected_edge(2, &0, &2);
+    g.insert_undirected_edge(2, &2, &2);
+    g.insert_undirected_edge(3, &3, &4);
+    g.insert_undirected_edge(3, &4, &5);
+    g.insert_undirected_edge(3, &3, &5);
+    for msf in [g.kruskal(), g.prim(), g.boruvka()] {
+        assert_eq!(7, msf.order());
+        assert_eq!(4, msf.size());
+        assert_eq!(3, msf.components().count());
+        assert_eq!(9, msf.edges().map(|e| ***e).sum::<u8>());
+        assert_eq!(0, msf.get_vertex(&&6).unwrap().degree());
+    }
+}
+
+#[test]
+fn topological_sort_dag() {
+    // undershorts, pants, belt, shirt, tie, jacket, socks, shoes, watch
+    let mut g = DiGraph::<&str, UnweightedEdge>::new();
+    let clothes = ["undershorts", "pants", "belt", "shirt", "tie", "jacket", "socks", "shoes", "watch"];
+    for c in &clothes { g.insert_vertex(*c); }
+    let deps = [
+        ("undershorts", "pants"), ("undershorts", "shoes"), ("pants", "belt"),
+        ("pants", "shoes"), ("belt", "jacket"), ("shirt", "belt"), ("shirt", "tie"),
+        ("tie", "jacket"), ("socks", "shoes"),
+    ];
+    for &(l, r) in &deps { g.insert_directed_edge(UnweightedEdge, &l, &r); }
+
+    let order = g.topological_sort().unwrap();
+    assert_eq!(clothes.len(), order.len());
+    let pos = |c: &str| order.iter().position(|v| *v.get() == c).unwrap();
+    for &(l, r) in &deps {
+        assert!(pos(l) < pos(r), "{} should come before {}", l, r);
+    }
+    assert_eq!(clothes.len(), g.topological().count());
+    assert!(g.topological().find_cycle().is_none());
+}
+
+#[test]
+fn topological_sort_cycle() {
+    let mut g = DiGraph::<u8, u8>::new();
+    for i in 0..6 { g.insert_vertex(i); }
+    g.insert_directed_edge(1, &0, &1);
+    g.insert_directed_edge(2, &1, &2);
+    g.insert_directed_edge(3, &2, &3);
+    g.insert_directed_edge(4, &3, &1);
+    g.insert_directed_edge(5, &3, &4);
+    g.insert_directed_edge(6, &5, &0);
+
+    let mut topo = g.topological();
+    let order: Vec<u8> = topo.by_ref().map(|v| *v.get()).collect();
+    assert_eq!(vec![5, 0], order);
+    let cycle = topo.find_cycle().unwrap();
+    assert_eq!(3, cycle.len());
+    let mut verts: Vec<u8> = cycle.path().vertices().skip(1).map(|v| *v.get()).collect();
+    verts.sort();
+    assert_eq!(vec![1, 2, 3], verts);
+    assert!(cycle.path().steps().all(|(l, e, r)| l.edge_to(r.get()) == Some(e)));
+    assert!(g.topological_sort().is_err());
+
+    g.insert_directed_edge(7, &5, &5);
+    let selfloop = g.topological_sort().unwrap_err();
+    assert!(selfloop.len() == 1 || selfloop.len() == 3);
+}
+
+// CLRS figure 22.9
+fn clrs_scc() -> DiGraph<char, UnweightedEdge> {
+    let mut g = Graph::directed();
+    for c in "abcdefgh".chars() { g.insert_vertex(c); }
+    for e in &["ab", "bc", "be", "bf", "cd", "cg", "dc", "dh", "ea", "ef", "fg", "gf", "gh", "hh"] {
+        let e: Vec<char> = e.chars().collect();
+        g.insert_directed_edge(UnweightedEdge, &e[0], &e[1]);
+    }
+    g
+}
+
+#[test]
+fn strong_components_clrs() {
+    let g = clrs_scc();
+    let components: Vec<String> = g.strong_components().map(|c| {
+        let mut c: Vec<char> = c.into_iter().map(|v| *v.get()).collect();
+        c.sort();
+        c.into_iter().collect()
+    }).collect();
+    let mut sorted = components.clone();
+    sorted.sort();
+    assert_eq!(vec!["abe", "cd", "fg", "h"], sorted);
+    // reverse topological: edges only point at earlier components
+    let pos = |v: char| components.iter().position(|c| c.contains(v)).unwrap();
+    for v in g.vertices() {
+        for w in g.get_children(v) {
+            assert!(pos(*w.get()) <= pos(*v.get()));
+        }
+    }
+
+    let dag = g.condensation();
+    assert_eq!(4, dag.order());
+    assert_eq!(5, dag.size());
+    let order = dag.topological_sort().unwrap();
+    assert!(order[0].get().contains(&&'a'));
+    assert_eq!(&vec![&'h'], order[3].get());
+}
+
+#[test]
+fn weak_components_dir() {
+    let mut g = clrs_scc();
+    for c in "xyz".chars() { g.insert_vertex(c); }
+    g.insert_directed_edge(UnweightedEdge, &'y', &'x');
+    let mut components: Vec<String> = g.weak_components().map(|c| {
+        let mut c: Vec<char> = c.into_iter().map(|v| *v.get()).collect();
+        c.sort();
+        c.into_iter().collect()
+    }).collect();
+    components.sort();
+    assert_eq!(vec!["abcdefgh", "xy", "z"], components);
+
+    // same answer no matter where iteration starts
+    let mut chain = DiGraph::<u8, UnweightedEdge>::new();
+    for i in 0..10 { chain.insert_vertex(i); }
+    for i in 1..10 { chain.insert_directed_edge(UnweightedEdge, &i, &(i-1)); }
+    let weak: Vec<_> = chain.weak_components().collect();
+    assert_eq!(1, weak.len());
+    assert_eq!(10, weak[0].len());
+}
+
+#[test]
+fn low_link_undir() {
+    // two triangles joined at 2, a tail 4-5-6 off 3, a doubled edge 6=7
+    //  and a lone self-loop on 8
+    let mut g = UndirectedGraph::<u8, u8>::new();
+    for i in 0..9 { g.insert_vertex(i); }
+    for &(w, l, r) in &[(1, 0, 1), (2, 1, 2), (3, 2, 0), (4, 2, 3), (5, 3, 4),
+                        (6, 4, 2), (7, 4, 5), (8, 5, 6), (9, 6, 7), (10, 6, 7), (11, 8, 8)] {
+        g.insert_undirected_edge(w, &l, &r);
+    }
+
+    let mut bridges: Vec<u8> = g.bridges().into_iter().map(|(_, &e, _)| e).collect();
+    bridges.sort();
+    assert_eq!(vec![7, 8], bridges);
+    for (l, e, r) in g.bridges() {
+        assert_eq!(Some(e), g.edge_between(l.get(), r.get()));
+    }
+
+    let mut cuts: Vec<u8> = g.articulation_points().into_iter().map(|v| *v.get()).collect();
+    cuts.sort();
+    assert_eq!(vec![2, 4, 5, 6], cuts);
+
+    let mut blocks: Vec<Vec<u8>> = g.biconnected_components().map(|b| {
+        let mut b: Vec<u8> = b.into_iter().map(|(_, &e, _)| e).collect();
+        b.sort();
+        b
+    }).collect();
+    blocks.sort();
+    assert_eq!(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7], vec![8], vec![9, 10], vec![11]], blocks);
+
+    let tree = numerical_tree(30);
+    assert_eq!(29, tree.bridges().len());
+    assert_eq!(29, tree.biconnected_components().count());
+    assert_eq!(3, tree.articulation_points().len());
+}
+
+#[test]
+fn bipartite_grid() {
+    let g = grid(6, &[(2, 2)]);
+    assert!(g.is_bipartite());
+    let (l, r) = g.bipartition().unwrap();
+    assert_eq!(g.order(), l.len() + r.len());
+    for half in &[l, r] {
+        for a in half {
+            assert!(half.iter().all(|b| a.reaches(b.get()) == false));
+        }
+    }
+    assert!(numerical_tree(100).is_bipartite());
+}
+
+#[test]
+fn bipartite_odd_cycle() {
+    let g = germany_wiki_map();
+    assert!(g.is_bipartite() == false);
+    let cycle = g.bipartition().unwrap_err();
+    assert_eq!(1, cycle.len() % 2);
+    assert_eq!(cycle.path().start().get(), cycle.path().end().get());
+    assert!(cycle.path().steps().all(|(l, e, r)| l.edge_to(r.get()) == Some(e)));
+
+    // two components, only the second of which has a (5-)cycle
+    let mut g = UndirectedGraph::<u8, u8>::new();
+    for i in 0..8 { g.insert_vertex(i); }
+    for &(l, r) in &[(0, 1), (1, 2), (3, 4), (4, 5), (5, 6), (6, 7), (7, 3)] {
+        g.insert_undirected_edge(l + r, &l, &r);
+    }
+    let cycle = g.bipartition().unwrap_err();
+    assert_eq!(5, cycle.len());
+    let mut verts: Vec<u8> = cycle.path().vertices().skip(1).map(|v| *v.get()).collect();
+    verts.sort();
+    assert_eq!(vec![3, 4, 5, 6, 7], verts);
+
+    g.insert_undirected_edge(0, &0, &0);
+    g.insert_undirected_edge(0, &3, &7);
+    assert!(g.bipartition().unwrap_err().len() % 2 == 1);
+}
+
+#[test]
+fn bipartite_dir() {
+    // jobs that can't share a slot, whichever way the constraint was written
+    let mut g = DiGraph::<char, UnweightedEdge>::new();
+    for c in "abcd".chars() { g.insert_vertex(c); }
+    for &(l, r) in &[('a', 'b'), ('c', 'b'), ('c', 'd'), ('d', 'a')] {
+        g.insert_directed_edge(UnweightedEdge, &l, &r);
+    }
+    let (l, r) = g.bipartition().unwrap();
+    let mut halves: Vec<Vec<char>> = [l, r].iter().map(|half| {
+        let mut half: Vec<char> = half.iter().map(|v| *v.get()).collect();
+        half.sort();
+        half
+    }).collect();
+    halves.sort();
+    assert_eq!(vec![vec!['a', 'c'], vec!['b', 'd']], halves);
+
+    g.insert_directed_edge(UnweightedEdge, &'a', &'c');
+    let cycle = g.bipartition().unwrap_err();
+    assert_eq!(3, cycle.len());
+    assert_eq!(cycle.path().start().get(), cycle.path().end().get());
+    assert!(cycle.path().steps().all(|(l, _, r)| l.reaches(r.get()) || r.reaches(l.get())));
+}
+
+// CLRS figure 26.1
+fn clrs_flow() -> DiGraph<&'static str, u32> {
+    let mut g = Graph::directed();
+    for v in &["s", "v1", "v2", "v3", "v4", "t"] { g.insert_vertex(*v); }
+    for &(c, l, r) in &[(16, "s", "v1"), (13, "s", "v2"), (4, "v2", "v1"), (12, "v1", "v3"),
+                        (9, "v3", "v2"), (14, "v2", "v4"), (7, "v4", "v3"), (20, "v3", "t"),
+                        (4, "v4", "t")] {
+        g.insert_directed_edge(c, &l, &r);
+    }
+    g
+}
+
+#[test]
+fn max_flow_clrs() {
+    let g = clrs_flow();
+    let (s, t) = (g.get_vertex(&"s").unwrap(), g.get_vertex(&"t").unwrap());
+    for flow in &[g.edmonds_karp(s, t), g.dinic(s, t)] {
+        assert_eq!(23, flow.value());
+        // capacity and conservation
+        for &((_, e, _), f) in flow.flows() {
+            assert!(f <= *e);
+            assert_eq!(Some(f), flow.flow(e));
+        }
+        for v in g.vertices().filter(|v| v.get() != &"s" && v.get() != &"t") {
+            let inflow: u32 = flow.flows().iter().filter(|f| (f.0).2.get() == v.get()).map(|f| f.1).sum();
+            let outflow: u32 = flow.flows().iter().filter(|f| (f.0).0.get() == v.get()).map(|f| f.1).sum();
+            assert_eq!(inflow, outflow);
+        }
+        // min cut capacity matches the flow
+        let side: Vec<&str> = flow.min_cut().iter().map(|v| *v.get()).collect();
+        assert!(side.contains(&"s") && side.contains(&"t") == false);
+        let cut: u32 = flow.flows().iter()
+            .filter(|f| side.contains((f.0).0.get()) && side.contains((f.0).2.get()) == false)
+            .map(|f| *(f.0).1)
+            .sum();
+        assert_eq!(23, cut);
+    }
+    let v1 = g.get_vertex(&"v1").unwrap();
+    assert_eq!(0, g.dinic(t, s).value());
+    assert_eq!(0, g.edmonds_karp(v1, v1).value());
+}
+
+#[test]
+fn min_cost_max_flow_dir() {
+    let mut g = DiGraph::<char, (i32, i32)>::new();
+    for c in "sabt".chars() { g.insert_vertex(c); }
+    g.insert_directed_edge((3, 1), &'s', &'a');
+    g.insert_directed_edge((2, 4), &'s', &'b');
+    g.insert_directed_edge((3, 1), &'a', &'b');
+    g.insert_directed_edge((2, 6), &'a', &'t');
+    g.insert_directed_edge((4, 1), &'b', &'t');
+    let (s, t) = (g.get_vertex(&'s').unwrap(), g.get_vertex(&'t').unwrap());
+    let flow = g.min_cost_max_flow(s, t);
+    assert_eq!(5, flow.value());
+    // s→a→b→t ×2, s→b→t ×2, s→a→t ×1
+    assert_eq!(2 * 3 + 2 * 5 + 7, flow.cost());
+    assert_eq!(Some(2), flow.flow(g.edge_between(&'a', &'b').unwrap()));
+    let total: i32 = flow.flows().iter().map(|&((_, e, _), f)| f * e.1).sum();
+    assert_eq!(flow.cost(), total);
+
+    // a→b→a now costs -4 a lap, but b→a only has room for one
+    g.insert_directed_edge((1, -5), &'b', &'a');
+    let (s, t) = (g.get_vertex(&'s').unwrap(), g.get_vertex(&'t').unwrap());
+    let flow = g.min_cost_max_flow(s, t);
+    assert_eq!(5, flow.value());
+    // s→a→b→t ×3, s→b→a→t ×1, s→b→t ×1
+    assert_eq!(3 * 3 + (4 - 5 + 6) + 5, flow.cost());
+    assert_eq!(Some(1), flow.flow(g.edge_between(&'b', &'a').unwrap()));
+    assert_eq!(Some(3), flow.flow(g.edge_between(&'a', &'b').unwrap()));
+    let total: i32 = flow.flows().iter().map(|&((_, e, _), f)| f * e.1).sum();
+    assert_eq!(flow.cost(), total);
+    // with nothing to route, the cycle is still worth a lap
+    let idle = g.min_cost_flow(|_| 0).unwrap();
+    assert_eq!((0, -4), (idle.value(), idle.cost()));
+    assert_eq!(-4, g.min_cost_max_flow(s, s).cost());
+}
+
+#[test]
+fn min_cost_flow_narrow_costs() {
+    // a hundred units at 100 + 100 apiece, in edges that fit in an i8
+    let mut g = DiGraph::<char, (i8, i8)>::new();
+    for c in "sat".chars() { g.insert_vertex(c); }
+    g.insert_directed_edge((100, 100), &'s', &'a');
+    g.insert_directed_edge((100, 100), &'a', &'t');
+    g.insert_directed_edge((50, -100), &'t', &'a');
+    let (s, t) = (g.get_vertex(&'s').unwrap(), g.get_vertex(&'t').unwrap());
+    let flow = g.min_cost_max_flow(s, t);
+    assert_eq!(100, flow.value());
+    // the cycle through t→a costs nothing a lap, so isn't worth running
+    assert_eq!(20_000, flow.cost());
+    let mut g = DiGraph::<char, (i16, i16)>::new();
+    for c in "ab".chars() { g.insert_vertex(c); }
+    g.insert_directed_edge((300, -300), &'a', &'b');
+    g.insert_directed_edge((300, -300), &'b', &'a');
+    assert_eq!(-180_000, g.min_cost_flow(|_| 0).unwrap().cost());
+}
+
+#[test]
+fn min_cost_flow_assignment() {
+    let mut g = DiGraph::<&str, (i64, i64)>::new();
+    for v in &["job1", "job2", "pool1", "pool2"] { g.insert_vertex(*v); }
+    g.insert_directed_edge((1, 4), &"job1", &"pool1");
+    g.insert_directed_edge((1, 1), &"job1", &"pool2");
+    g.insert_directed_edge((1, 2), &"job2", &"pool1");
+    g.insert_directed_edge((1, 5), &"job2", &"pool2");
+    let demand = |v: &&str| if v.starts_with("job") { -1 } else { 1 };
+    let flow = g.min_cost_flow(demand).unwrap();
+    assert_eq!(2, flow.value());
+    assert_eq!(3, flow.cost());
+    assert_eq!(Some(1), flow.flow(g.edge_between(&"job1", &"pool2").unwrap()));
+    assert_eq!(Some(0), flow.flow(g.edge_between(&"job1", &"pool1").unwrap()));
+
+    // both jobs want pool1, which only one can reach
+    g.remove_edge(&"job2", &"pool1");
+    let crowded = |v: &&str| match *v { "pool1" => 2, "pool2" => 0, _ => -1 };
+    assert_eq!(Some(::FlowError::Infeasible { routed: 1, required: 2 }),
+               g.min_cost_flow(crowded).err());
+    let unbalanced = |v: &&str| if *v == "job1" { -1 } else { 0 };
+    assert!(g.min_cost_flow(unbalanced).is_err());
+}
+
+#[test]
+fn bipartite_matching_reviewers() {
+    let mut g = UndirectedGraph::<&str, UnweightedEdge>::new();
+    let reviewers = ["ann", "bob", "cat", "dan", "eve"];
+    let changes = ["c1", "c2", "c3", "c4", "c5"];
+    for v in reviewers.iter().chain(changes.iter()) { g.insert_vertex(*v); }
+    // eve can't review anything and c5 has a single reviewer who's also c1's
+    for &(r, c) in &[("ann", "c1"), ("ann", "c2"), ("bob", "c1"), ("cat", "c2"),
+                     ("cat", "c3"), ("cat", "c4"), ("dan", "c1"), ("dan", "c5")] {
+        g.insert_undirected_edge(UnweightedEdge, &r, &c);
+    }
+    let (matching, cover) = g.bipartite_matching_by(|v| reviewers.contains(v));
+    assert_eq!(4, matching.len());
+    assert_eq!(matching.len(), cover.len());
+    for &(l, _, r) in matching.edges() {
+        assert!(reviewers.contains(l.get()) && changes.contains(r.get()));
+        assert_eq!(r.get(), matching.mate(l.get()).unwrap().get());
+        assert!(l.reaches(r.get()));
+    }
+    assert!(matching.is_matched(&"eve") == false);
+    // every edge has an endpoint in the cover
+    for v in g.vertices() {
+        for n in g.get_neighbors(v) {
+            assert!(cover.iter().any(|c| c.get() == v.get() || c.get() == n.get()));
+        }
+    }
+
+    let (matching, cover) = g.bipartite_matching().unwrap();
+    assert_eq!(4, matching.len());
+    assert_eq!(4, cover.len());
+    assert!(germany_wiki_map().bipartite_matching().is_err());
+}
+
+#[test]
+fn bipartite_matching_grid() {
+    // perfect matchings exist on an even grid, but not with a corner missing
+    let g = grid(6, &[]);
+    assert_eq!(18, g.bipartite_matching().unwrap().0.len());
+    let g = grid(6, &[(0, 0)]);
+    let (matching, cover) = g.bipartite_matching().unwrap();
+    assert_eq!(17, matching.len());
+    assert_eq!(17, cover.len());
+}
+
+fn weighted(edges: &[(u8, u8, i32)]) -> UndirectedGraph<u8, i32> {
+    let mut g = Graph::new();
+    for &(l, r, w) in edges {
+        g.insert_vertex(l);
+        g.insert_vertex(r);
+        g.insert_undirected_edge(w, &l, &r);
+    }
+    g
+}
+
+// heaviest matching using only the edges from `k` on, by trying everything
+fn brute_matching(edges: &[(u8, u8, i32)], k: usize, used: &mut HashSet<u8>) -> i32 {
+    if k == edges.len() { return 0 }
+    let (l, r, w) = edges[k];
+    let mut best = brute_matching(edges, k + 1, used);
+    if w > 0 && l != r && used.contains(&l) == false && used.contains(&r) == false {
+        used.insert(l);
+        used.insert(r);
+        best = best.max(w + brute_matching(edges, k + 1, used));
+        used.remove(&l);
+        used.remove(&r);
+    }
+    best
+}
+
+#[test]
+fn max_weight_matching_blossoms() {
+    // cases that need blossoms to be built, relabeled, and expanded mid-stage
+    let cases: &[(&[(u8, u8, i32)], i32)] = &[
+        (&[(1, 2, 10), (2, 3, 11)], 11),
+        (&[(1, 2, 5), (2, 3, 11), (3, 4, 5)], 11),
+        (&[(1, 2, 8), (1, 3, 9), (2, 3, 10), (3, 4, 7), (1, 6, 5), (4, 5, 6)], 21),
+        (&[(1, 2, 9), (1, 3, 9), (2, 3, 10), (2, 4, 8), (3, 5, 8), (4, 5, 10), (5, 6, 6)], 23),
+        (&[(1, 2, 23), (1, 5, 22), (1, 6, 15), (2, 3, 25), (3, 4, 22), (4, 5, 25), 
+           (4, 8, 14), (5, 7, 13)], 67),
+        (&[(1, 2, 45), (1, 5, 45), (2, 3, 50), (3, 4, 45), (4, 5, 50), (1, 6, 30), 
+           (3, 9, 35), (4, 8, 35), (5, 7, 26), (9, 10, 5)], 146),
+        (&[(1, 2, 45), (1, 5, 45), (2, 3, 50), (3, 4, 45), (4, 5, 50), (1, 6, 30), 
+           (3, 9, 35), (4, 8, 26), (5, 7, 40), (9, 10, 5)], 151),
+        (&[(1, 2, 40), (1, 3, 40), (2, 3, 60), (2, 4, 55), (3, 5, 55), (4, 5, 50), 
+           (1, 8, 15), (5, 7, 30), (7, 6, 10), (8, 10, 10), (4, 9, 30)], 145),
+        (&[(1, 2, -3), (2, 3, 0), (3, 1, 4)], 4),
+    ];
+    for &(edges, weight) in cases {
+        let g = weighted(edges);
+        let matching = g.max_weight_matching();
+        assert_eq!(weight, matching.edges().iter().map(|&(_, &w, _)| w).sum::<i32>());
+        assert_eq!(weight, brute_matching(edges, 0, &mut HashSet::new()));
+        for &(l, _, r) in matching.edges() {
+            assert_eq!(r.get(), matching.mate(l.get()).unwrap().get());
+            assert_eq!(l.get(), matching.mate(r.get()).unwrap().get());
+        }
+    }
+}
+
+#[test]
+fn max_weight_matching_huge_weights() {
+    let max = u64::MAX;
+    let mut g = UndirectedGraph::<u8, u64>::new();
+    for i in 1..6 { g.insert_vertex(i); }
+    for &(l, r, w) in &[(1, 2, max), (2, 3, max - 1), (3, 1, max - 2), (3, 4, 1), (4, 5, max / 2)] {
+        g.insert_undirected_edge(w, &l, &r);
+    }
+    let matching = g.max_weight_matching();
+    let mut weights: Vec<u64> = matching.edges().iter().map(|&(_, &w, _)| w).collect();
+    weights.sort();
+    assert_eq!(vec![max / 2, max], weights);
+    assert!(matching.mate(&3).is_none());
+}
+
+#[test]
+fn max_weight_matching_random() {
+    let mut seed: u32 = 2017;
+    let mut rand = move |n: u32| {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        (seed >> 16) % n
+    };
+    for _ in 0..200 {
+        let (n, m) = (2 + rand(8) as u8, rand(16) as usize);
+        let edges: Vec<(u8, u8, i32)> = (0..m)
+            .map(|_| (rand(n as u32) as u8, rand(n as u32) as u8, rand(20) as i32 - 3))
+            .collect();
+        let g = weighted(&edges);
+        let weight = g.max_weight_matching().edges().iter().map(|&(_, &w, _)| w).sum::<i32>();
+        assert_eq!(brute_matching(&edges, 0, &mut HashSet::new()), weight, "{:?}", edges);
+
+        let unit: Vec<_> = edges.iter().map(|&(l, r, _)| (l, r, 1)).collect();
+        let size = weighted(&unit).max_cardinality_matching().len() as i32;
+        assert_eq!(brute_matching(&unit, 0, &mut HashSet::new()), size, "{:?}", edges);
+    }
+}
+
+fn petersen() -> UnweightedUndirectedGraph<u8> {
+    // two 5-cycles (one drawn as a star) joined by a perfect matching
+    let mut g = UnweightedUndirectedGraph::new();
+    for i in 0..10 { g.insert_vertex(i); }
+    for i in 0..5 {
+        g.insert_undirected_edge(UnweightedEdge, &i, &((i + 1) % 5));
+        g.insert_undirected_edge(UnweightedEdge, &(5 + i), &(5 + (i + 2) % 5));
+        g.insert_undirected_edge(UnweightedEdge, &i, &(5 + i));
+    }
+    g
+}
+
+#[test]
+fn max_cardinality_matching_odd_cycles() {
+    let g = petersen();
+    assert!(g.bipartite_matching().is_err());
+    let matching = g.max_cardinality_matching();
+    assert_eq!(5, matching.len());
+    assert!(g.vertices().all(|v| matching.is_matched(v.get())));
+
+    // a triangle hanging off the end of a path only has room for two
+    let mut g = UnweightedUndirectedGraph::<u8>::new();
+    for i in 0..5 { g.insert_vertex(i); }
+    for &(l, r) in &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 2)] {
+        g.insert_undirected_edge(UnweightedEdge, &l, &r);
+    }
+    assert_eq!(2, g.max_cardinality_matching().len());
+    assert_eq!(0, UnweightedUndirectedGraph::<u8>::new().max_cardinality_matching().len());
+}
+
+// workers 'a'.. and jobs 'A'.. joined wherever the cost isn't `None`
+fn jobs(costs: &[&[Option<u32>]]) -> UndirectedGraph<char, u32> {
+    let mut g = Graph::new();
+    for i in 0 .. costs.len() as u8 {
+        g.insert_vertex((b'a' + i) as char);
+        g.insert_vertex((b'A' + i) as char);
+    }
+    for (i, row) in costs.iter().enumerate() {
+        for (j, c) in row.iter().enumerate() {
+            if let Some(c) = *c {
+                g.insert_undirected_edge(c, &((b'a' + i as u8) as char), &((b'A' + j as u8) as char));
+            }
+        }
+    }
+    g
+}
+
+// cheapest and dearest ways to give row `i` onwards a distinct column each
+fn brute_assignment(costs: &[&[Option<u32>]], i: usize, used: &mut Vec<bool>) -> Option<(u32, u32)> {
+    if i == costs.len() { return Some((0, 0)) }
+    let mut best: Option<(u32, u32)> = None;
+    for j in 0 .. costs.len() {
+        if let (false, Some(c)) = (used[j], costs[i][j]) {
+            used[j] = true;
+            if let Some((lo, hi)) = brute_assignment(costs, i + 1, used) {
+                best = Some(match best {
+                    Some((blo, bhi)) => (blo.min(lo + c), bhi.max(hi + c)),
+                    None => (lo + c, hi + c),
+                });
+            }
+            used[j] = false;
+        }
+    }
+    best
+}
+
+#[test]
+fn assignment_complete() {
+    let costs: &[&[Option<u32>]] = &[
+        &[Some(9), Some(2), Some(7), Some(8)],
+        &[Some(6), Some(4), Some(3), Some(7)],
+        &[Some(5), Some(8), Some(1), Some(8)],
+        &[Some(7), Some(6), Some(9), Some(4)],
+    ];
+    let g = jobs(costs);
+    let cheapest = g.min_cost_assignment_by(|c| c.is_lowercase()).unwrap();
+    assert_eq!(13, cheapest.cost());
+    assert_eq!('B', *cheapest.matching().mate(&'a').unwrap().get());
+    assert_eq!('C', *cheapest.matching().mate(&'c').unwrap().get());
+    let dearest = g.max_weight_assignment().unwrap();
+    assert_eq!(33, dearest.cost());
+    let matching = dearest.into_matching();
+    assert_eq!(4, matching.len());
+    assert_eq!(33, matching.edges().iter().map(|&(_, &w, _)| w).sum::<u32>());
+}
+
+#[test]
+fn assignment_sparse() {
+    let mut seed: u32 = 1955;
+    let mut rand = move |n: u32| {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        (seed >> 16) % n
+    };
+    for _ in 0..200 {
+        let n = 1 + rand(6) as usize;
+        let rows: Vec<Vec<Option<u32>>> = (0..n).map(|_| {
+            (0..n).map(|_| if rand(3) == 0 { None } else { Some(rand(50)) }).collect()
+        }).collect();
+        let costs: Vec<&[Option<u32>]> = rows.iter().map(|r| &r[..]).collect();
+        let g = jobs(&costs);
+        let is_worker = |c: &char| c.is_lowercase();
+        match brute_assignment(&costs, 0, &mut vec![false; n]) {
+            Some((lo, hi)) => {
+                assert_eq!(lo, g.min_cost_assignment_by(is_worker).unwrap().cost());
+                assert_eq!(hi, g.max_weight_assignment_by(is_worker).unwrap().cost());
+            }
+            None => assert!(g.min_cost_assignment_by(is_worker).is_err()),
+        }
+    }
+}
+
+#[test]
+fn assignment_errors() {
+    // 'a' and 'b' can only take job 'A'
+    let costs: &[&[Option<u32>]] = &[
+        &[Some(1), None, None],
+        &[Some(2), None, None],
+        &[Some(3), Some(4), Some(5)],
+    ];
+    match jobs(costs).min_cost_assignment_by(|c| c.is_lowercase()) {
+        Err(::AssignmentError::Deficient { vertices, neighbors }) => {
+            let mut vertices: Vec<char> = vertices.iter().map(|v| *v.get()).collect();
+            vertices.sort();
+            assert_eq!(vec!['a', 'b'], vertices);
+            assert_eq!(1, neighbors.len());
+            assert_eq!('A', *neighbors[0].get());
+        }
+        other => panic!("expected a deficiency, got {:?}", other),
+    }
+    // more workers than jobs
+    let mut g = jobs(&[&[Some(1)]]);
+    g.insert_vertex('b');
+    g.insert_undirected_edge(1, &'b', &'A');
+    match g.max_weight_assignment() {
+        Err(::AssignmentError::Deficient { vertices, neighbors }) => {
+            assert_eq!(2, vertices.len());
+            assert_eq!(1, neighbors.len());
+        }
+        other => panic!("expected a deficiency, got {:?}", other),
+    }
+    let mut triangle = jobs(&[&[Some(1)]]);
+    triangle.insert_vertex('b');
+    triangle.insert_undirected_edge(1, &'b', &'A');
+    triangle.insert_undirected_edge(1, &'b', &'a');
+    match triangle.min_cost_assignment() {
+        Err(::AssignmentError::NotBipartite(cycle)) => assert_eq!(3, cycle.len()),
+        other => panic!("expected an odd cycle, got {:?}", other),
+    }
+}
+
+#[test]
+fn eulerian_undir() {
+    // Königsberg: all four land masses touch an odd number of bridges
+    let mut g = UndirectedGraph::<char, u8>::new();
+    for c in "NSIE".chars() { g.insert_vertex(c); }
+    for (i, &(l, r)) in [('N', 'I'), ('N', 'I'), ('S', 'I'), ('S', 'I'), 
+                         ('N', 'E'), ('S', 'E'), ('I', 'E')].iter().enumerate() {
+        g.insert_undirected_edge(i as u8, &l, &r);
+    }
+    assert!(g.eulerian_circuit().is_none());
+    assert!(g.eulerian_path().is_none());
+
+    // once one bridge is gone a walk exists between the remaining odd vertices
+    g.remove_edge(&'I', &'E');
+    assert!(g.eulerian_circuit().is_none());
+    let path = g.eulerian_path().unwrap();
+    assert_eq!(6, path.len());
+    let ends: HashSet<char> = [*path.start().get(), *path.end().get()].iter().cloned().collect();
+    assert_eq!(['N', 'S'].iter().cloned().collect::<HashSet<_>>(), ends);
+    let bridges: HashSet<u8> = path.edges().map(|&&e| e).collect();
+    assert_eq!(6, bridges.len());
+    for (l, e, r) in path.steps() {
+        assert!(g.get_neighbors(l).with_edges().any(|(n, f)| n.get() == r.get() && f == e));
+    }
+
+    // joining them makes every vertex even, so the walk can be closed
+    g.insert_undirected_edge(7, &'N', &'S');
+    g.insert_undirected_edge(8, &'E', &'E');
+    let cycle = g.eulerian_circuit().unwrap();
+    assert_eq!(8, cycle.len());
+    assert_eq!(cycle.path().start().get(), cycle.path().end().get());
+
+    // even degrees aren't enough if the edges are in separate pieces
+    let mut g = UnweightedUndirectedGraph::<u8>::new();
+    for i in 0..6 { g.insert_vertex(i); }
+    for &(l, r) in &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)] {
+        g.insert_undirected_edge(UnweightedEdge, &l, &r);
+    }
+    assert!(g.eulerian_circuit().is_none());
+    assert!(g.eulerian_path().is_none());
+
+    let mut g = UnweightedUndirectedGraph::<u8>::new();
+    g.insert_vertex(0);
+    assert_eq!(0, g.eulerian_circuit().unwrap().len());
+}
+
+#[test]
+fn eulerian_dir() {
+    // de Bruijn: every 3-bit string once, each edge shifting one bit in
+    let mut g = DiGraph::<u8, u8>::new();
+    for v in 0..4 { g.insert_vertex(v); }
+    for e in 0..8 {
+        g.insert_directed_edge(e, &(e >> 1), &(e & 3));
+    }
+    assert!(g.vertices().all(|v| v.in_degree() == 2 && v.out_degree() == 2));
+    let cycle = g.eulerian_circuit().unwrap();
+    assert_eq!(8, cycle.len());
+    let seen: HashSet<u8> = cycle.path().edges().map(|&&e| e).collect();
+    assert_eq!(8, seen.len());
+    for (l, &e, r) in cycle.path().steps() {
+        assert_eq!((e >> 1, e & 3), (*l.get(), *r.get()));
+    }
+
+    // an edge out of the cycle leaves a path that has to end there
+    g.insert_vertex(4);
+    g.insert_directed_edge(8, &0, &4);
+    assert!(g.eulerian_circuit().is_none());
+    let path = g.eulerian_path().unwrap();
+    assert_eq!(9, path.len());
+    assert_eq!((0, 4), (*path.start().get(), *path.end().get()));
+
+    // two vertices with an extra edge out can't both be the start
+    g.insert_directed_edge(9, &3, &4);
+    assert!(g.eulerian_path().is_none());
+}
+
+#[test]
+fn hamiltonian_petersen() {
+    let g = petersen();
+    assert!(g.hamiltonian_cycle().is_none());
+    let path = g.hamiltonian_path().unwrap();
+    assert_eq!(9, path.len());
+    let seen: HashSet<u8> = path.vertices().map(|v| *v.get()).collect();
+    assert_eq!(10, seen.len());
+    for (l, _, r) in path.steps() {
+        assert!(l.reaches(r.get()));
+    }
+
+    // one more edge and it's a cycle
+    let mut g = UnweightedUndirectedGraph::<u8>::new();
+    for i in 0..6 { g.insert_vertex(i); }
+    for &(l, r) in &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (0, 3)] {
+        g.insert_undirected_edge(UnweightedEdge, &l, &r);
+    }
+    assert!(g.hamiltonian_cycle().is_none());
+    assert_eq!(5, g.hamiltonian_path().unwrap().len());
+    g.insert_undirected_edge(UnweightedEdge, &5, &0);
+    let cycle = g.hamiltonian_cycle().unwrap();
+    assert_eq!(6, cycle.len());
+
+    // a cycle through two vertices needs two edges between them
+    let mut g = UnweightedUndirectedGraph::<u8>::new();
+    g.insert_vertex(0);
+    g.insert_vertex(1);
+    g.insert_undirected_edge(UnweightedEdge, &0, &1);
+    assert!(g.hamiltonian_cycle().is_none());
+    g.insert_undirected_edge(UnweightedEdge, &1, &0);
+    assert_eq!(2, g.hamiltonian_cycle().unwrap().len());
+}
+
+#[test]
+fn hamiltonian_dir() {
+    // a tournament always has a Hamiltonian path: 0→1→2→3 with back edges
+    let mut g = DiGraph::<u8, UnweightedEdge>::new();
+    for i in 0..4 { g.insert_vertex(i); }
+    for &(l, r) in &[(0, 1), (1, 2), (2, 3), (2, 0), (3, 0), (3, 1)] {
+        g.insert_directed_edge(UnweightedEdge, &l, &r);
+    }
+    let path = g.hamiltonian_path().unwrap();
+    assert_eq!(3, path.len());
+    for (l, _, r) in path.steps() {
+        assert!(l.reaches(r.get()));
+    }
+    let cycle = g.hamiltonian_cycle().unwrap();
+    assert_eq!(4, cycle.len());
+    assert!(g.remove_edge(&3, &0).is_some());
+    assert!(g.hamiltonian_cycle().is_none());
+}
+
+// cities on a grid, all joined by their Manhattan distance
+fn cities(coords: &[(i32, i32)]) -> UndirectedGraph<(i32, i32), i32> {
+    let mut g = Graph::new();
+    for &c in coords { g.insert_vertex(c); }
+    for (i, &a) in coords.iter().enumerate() {
+        for &b in &coords[i + 1 ..] {
+            g.insert_undirected_edge((a.0 - b.0).abs() + (a.1 - b.1).abs(), &a, &b);
+        }
+    }
+    g
+}
+
+// length of the shortest tour from `here` through everything left and back to `home`
+fn brute_tour(g: &UndirectedGraph<(i32, i32), i32>, home: (i32, i32), here: (i32, i32), 
+              left: &mut Vec<(i32, i32)>) -> i32 
+{
+    if left.is_empty() { return *g.edge_between(&here, &home).unwrap() }
+    let mut best = i32::MAX;
+    for i in 0 .. left.len() {
+        let next = left.swap_remove(i);
+        best = best.min(g.edge_between(&here, &next).unwrap() + brute_tour(g, home, next, left));
+        left.push(next);
+        let last = left.len() - 1;
+        left.swap(i, last);
+    }
+    best
+}
+
+#[test]
+fn tsp_cities() {
+    let coords = [(0, 0), (4, 1), (7, 3), (2, 6), (5, 8), (9, 9), (1, 3), (8, 5)];
+    let g = cities(&coords);
+    let optimal = brute_tour(&g, coords[0], coords[0], &mut coords[1..].to_vec());
+    let tour = g.held_karp().unwrap();
+    assert_eq!(optimal, tour.path().weight());
+    assert_eq!(8, tour.len());
+    let seen: HashSet<_> = tour.path().vertices().map(|v| *v.get()).collect();
+    assert_eq!(8, seen.len());
+
+    for walk in [g.two_opt().unwrap(), g.christofides().unwrap()].iter() {
+        assert_eq!(walk.start().get(), walk.end().get());
+        let seen: HashSet<_> = walk.vertices().map(|v| *v.get()).collect();
+        assert_eq!(8, seen.len());
+        assert!(walk.weight() >= optimal);
+        assert!(2 * walk.weight() <= 3 * optimal);
+    }
+
+    // one city only needs a loop back to itself
+    let mut g = cities(&[(3, 3)]);
+    assert!(g.held_karp().is_none());
+    g.insert_undirected_edge(2, &(3, 3), &(3, 3));
+    assert_eq!(2, g.held_karp().unwrap().path().weight());
+    assert_eq!(0, g.two_opt().unwrap().len());
+
+    // too many cities to search exactly, but not to approximate
+    let coords: Vec<(i32, i32)> = (0..21).map(|i| (i % 7, i / 7)).collect();
+    let g = cities(&coords);
+    assert!(g.held_karp().is_none());
+    assert_eq!(21, g.christofides().unwrap().len());
+}
+
+#[test]
+fn tsp_germany() {
+    let g = germany_wiki_map();
+    // Erfurt only borders Würzburg, so no cycle can pass through it
+    assert!(g.held_karp().is_none());
+    for walk in [g.two_opt().unwrap(), g.christofides().unwrap()].iter() {
+        assert_eq!(walk.start().get(), walk.end().get());
+        let seen: HashSet<_> = walk.vertices().map(|v| *v.get()).collect();
+        assert_eq!(g.order(), seen.len());
+        for (l, e, r) in walk.steps() {
+            assert_eq!(Some(e), l.edge_to(r.get()));
+        }
+    }
+    let mut g = germany_wiki_map();
+    g.insert_vertex("Berlin");
+    assert!(g.two_opt().is_none());
+    assert!(g.christofides().is_none());
+}
+
+#[test]
+fn christofides_huge_weights() {
+    // every vertex of the star-shaped spanning tree is odd, and matching
+    //  three pairs of them at two billion apiece won't fit in a u32
+    let mut g = UndirectedGraph::<u8, u32>::new();
+    for i in 0..6 { g.insert_vertex(i); }
+    for i in 0..6u8 {
+        for j in i + 1 .. 6 {
+            let w = if i == 0 { 2_000_000_000 } else { 2_000_000_100 + (i + j) as u32 };
+            g.insert_undirected_edge(w, &i, &j);
+        }
+    }
+    let walk = g.christofides().unwrap();
+    assert_eq!(walk.start().get(), walk.end().get());
+    let seen: HashSet<_> = walk.vertices().map(|v| *v.get()).collect();
+    assert_eq!(6, seen.len());
+}
+
+#[test]
+fn isomorphic_undir() {
+    // relabeling the Petersen graph doesn't change it
+    let g = petersen();
+    let mut relabeled = UnweightedUndirectedGraph::<u8>::new();
+    let perm = [3, 7, 0, 9, 4, 1, 8, 2, 6, 5];
+    for &v in &perm { relabeled.insert_vertex(v); }
+    for i in 0..5u8 {
+        for &(l, r) in &[(i, (i + 1) % 5), (5 + i, 5 + (i + 2) % 5), (i, 5 + i)] {
+            relabeled.insert_undirected_edge(UnweightedEdge, &perm[l as usize], &perm[r as usize]);
+        }
+    }
+    assert!(g.is_isomorphic(&relabeled));
+
+    // the pentagonal prism is also cubic on ten vertices, but has 4-cycles
+    let mut prism = UnweightedUndirectedGraph::<u8>::new();
+    for i in 0..10 { prism.insert_vertex(i); }
+    for i in 0..5 {
+        prism.insert_undirected_edge(UnweightedEdge, &i, &((i + 1) % 5));
+        prism.insert_undirected_edge(UnweightedEdge, &(5 + i), &(5 + (i + 1) % 5));
+        prism.insert_undirected_edge(UnweightedEdge, &i, &(5 + i));
+    }
+    assert!(g.is_isomorphic(&prism) == false);
+    assert!(prism.is_isomorphic(&prism));
+
+    // weights only matter when asked to
+    let a = weighted(&[(1, 2, 1), (2, 3, 2), (3, 1, 3), (3, 4, 3)]);
+    let b = weighted(&[(5, 6, 1), (6, 7, 2), (7, 5, 3), (7, 8, 3)]);
+    let c = weighted(&[(5, 6, 1), (6, 7, 2), (7, 5, 3), (6, 8, 3)]);
+    assert!(a.is_isomorphic_by(&b, |_, _| true, |x, y| x == y));
+    assert!(a.is_isomorphic(&c));
+    assert!(a.is_isomorphic_by(&c, |_, _| true, |x, y| x == y) == false);
+    assert!(a.is_isomorphic_by(&b, |x, y| x + 4 == *y, |_, _| true));
+    assert!(a.is_isomorphic_by(&b, |x, y| x + 5 == *y, |_, _| true) == false);
+
+    // parallel edges pair up one to one
+    let a = weighted(&[(1, 2, 1), (1, 2, 2)]);
+    let b = weighted(&[(1, 2, 1), (1, 2, 1)]);
+    let c = weighted(&[(1, 2, 2), (1, 2, 1)]);
+    assert!(a.is_isomorphic_by(&b, |_, _| true, |x, y| x == y) == false);
+    assert!(b.is_isomorphic_by(&a, |_, _| true, |x, y| x == y) == false);
+    assert!(a.is_isomorphic_by(&c, |_, _| true, |x, y| x == y));
+    // the 2 can stand for either of `a`'s but the 1 only for the 1
+    assert!(c.is_isomorphic_by(&a, |_, _| true, |x, y| x >= y));
+    assert!(a.is_isomorphic_by(&b, |_, _| true, |x, y| x >= y));
+    assert!(b.is_isomorphic_by(&a, |_, _| true, |x, y| x >= y) == false);
+}
+
+#[test]
+fn subgraph_isomorphisms_undir() {
+    let mut k4 = UnweightedUndirectedGraph::<u8>::new();
+    for i in 0..4 { k4.insert_vertex(i); }
+    for i in 0..4 {
+        for j in i + 1 .. 4 {
+            k4.insert_undirected_edge(UnweightedEdge, &i, &j);
+        }
+    }
+    let mut triangle = UnweightedUndirectedGraph::<u8>::new();
+    let mut path = UnweightedUndirectedGraph::<u8>::new();
+    for i in 0..3 {
+        triangle.insert_vertex(i);
+        path.insert_vertex(i);
+    }
+    for &(l, r) in &[(0, 1), (1, 2)] {
+        triangle.insert_undirected_edge(UnweightedEdge, &l, &r);
+        path.insert_undirected_edge(UnweightedEdge, &l, &r);
+    }
+    triangle.insert_undirected_edge(UnweightedEdge, &2, &0);
+
+    // four triangles, each in six orientations
+    assert_eq!(24, k4.subgraph_isomorphisms(&triangle).count());
+    // every three vertices of K4 form a triangle, so no path is induced
+    assert_eq!(0, k4.subgraph_isomorphisms(&path).count());
+    // Petersen has no triangles, and three paths through each vertex
+    let g = petersen();
+    assert_eq!(0, g.subgraph_isomorphisms(&triangle).count());
+    let paths: Vec<_> = g.subgraph_isomorphisms(&path).collect();
+    assert_eq!(10 * 3 * 2, paths.len());
+    for m in &paths {
+        assert_eq!(3, m.len());
+        let v = g.get_vertex(m[&1]).unwrap();
+        assert!(v.reaches(m[&0]) && v.reaches(m[&2]));
+    }
+    // only centered on the outer ring
+    let outer: Vec<_> = g.subgraph_isomorphisms_by(&path, |&h, &p| p != 1 || h < 5, |_, _| true)
+        .collect();
+    assert_eq!(5 * 3 * 2, outer.len());
+    let centers: HashSet<u8> = outer.iter().map(|m| *m[&1]).collect();
+    assert_eq!((0..5).collect::<HashSet<u8>>(), centers);
+    assert_eq!(1, triangle.subgraph_isomorphisms(&UnweightedUndirectedGraph::new()).count());
+}
+
+#[test]
+fn isomorphic_dir() {
+    let mut cycle = DiGraph::<u8, UnweightedEdge>::new();
+    let mut backwards = DiGraph::<u8, UnweightedEdge>::new();
+    for i in 0..3 {
+        cycle.insert_vertex(i);
+        backwards.insert_vertex(i);
+    }
+    for i in 0..3 {
+        cycle.insert_directed_edge(UnweightedEdge, &i, &((i + 1) % 3));
+        backwards.insert_directed_edge(UnweightedEdge, &((i + 1) % 3), &i);
+    }
+    assert!(cycle.is_isomorphic(&backwards));
+
+    // a chain and a fork have the same shape if direction is ignored
+    let mut chain = DiGraph::<u8, UnweightedEdge>::new();
+    let mut fork = DiGraph::<u8, UnweightedEdge>::new();
+    for i in 0..3 {
+        chain.insert_vertex(i);
+        fork.insert_vertex(i);
+    }
+    chain.insert_directed_edge(UnweightedEdge, &0, &1);
+    chain.insert_directed_edge(UnweightedEdge, &1, &2);
+    fork.insert_directed_edge(UnweightedEdge, &1, &0);
+    fork.insert_directed_edge(UnweightedEdge, &1, &2);
+    assert!(chain.is_isomorphic(&fork) == false);
+
+    // every edge of the cycle is an induced copy of a single edge, but the
+    //  chain's ends aren't joined the way the cycle's are
+    let mut edge = DiGraph::<u8, UnweightedEdge>::new();
+    edge.insert_vertex(0);
+    edge.insert_vertex(1);
+    edge.insert_directed_edge(UnweightedEdge, &0, &1);
+    assert_eq!(3, cycle.subgraph_isomorphisms(&edge).count());
+    assert_eq!(0, cycle.subgraph_isomorphisms(&chain).count());
+    assert_eq!(1, chain.subgraph_isomorphisms(&chain).count());
+    assert_eq!(2, fork.subgraph_isomorphisms(&edge).count());
+}
+
+#[test]
+fn maximal_cliques() {
+    // K4 sharing a vertex with a triangle, a pendant edge with a self-loop,
+    //  and an isolated vertex
+    let edges = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3), (3, 4), (4, 5), (5, 3), (5, 6), (6, 6)];
+    let mut g = UnweightedUndirectedGraph::<u8>::new();
+    for i in 0..8 { g.insert_vertex(i); }
+    for &(l, r) in &edges { g.insert_undirected_edge(UnweightedEdge, &l, &r); }
+    let mut cliques: Vec<HashSet<u8>> = g.maximal_cliques()
+        .map(|c| c.into_iter().map(|v| *v.get()).collect())
+        .collect();
+    cliques.sort_by_key(|c| c.iter().min().cloned());
+    let expected: Vec<HashSet<u8>> = vec![vec![0, 1, 2, 3], vec![3, 4, 5], vec![5, 6], vec![7]]
+        .into_iter().map(|c| c.into_iter().collect()).collect();
+    assert_eq!(expected, cliques);
+    let biggest: HashSet<u8> = g.maximum_clique().into_iter().map(|v| *v.get()).collect();
+    assert_eq!(expected[0], biggest);
+
+    // no triangles, so just the edges
+    let g = petersen();
+    assert_eq!(15, g.maximal_cliques().count());
+    assert!(g.maximal_cliques().all(|c| c.len() == 2 && c[0].reaches(c[1].get())));
+    assert_eq!(2, g.maximum_clique().len());
+    assert!(UnweightedUndirectedGraph::<u8>::new().maximum_clique().is_empty());
+}
+
+#[test]
+fn maximal_cliques_random() {
+    let mut seed: u32 = 1962;
+    let mut rand = move |n: u32| {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        (seed >> 16) % n
+    };
+    for _ in 0..100 {
+        let n = 1 + rand(9) as u8;
+        let mut g = UnweightedUndirectedGraph::<u8>::new();
+        for i in 0..n { g.insert_vertex(i); }
+        let mut adj = vec![vec![false; n as usize]; n as usize];
+        for l in 0..n {
+            for r in l + 1 .. n {
+                if rand(3) > 0 {
+                    g.insert_undirected_edge(UnweightedEdge, &l, &r);
+                    adj[l as usize][r as usize] = true;
+                    adj[r as usize][l as usize] = true;
+                }
+            }
+        }
+        // every subset that's complete with nothing else adjacent to all of it
+        let mut expected: Vec<u32> = (1u32 .. 1 << n).filter(|&set| {
+            let has = |i: usize| set & (1 << i) != 0;
+            let complete = (0..n as usize).all(|i| (0..i).all(|j| has(i) == false || has(j) == false || adj[i][j]));
+            let joinable = (0..n as usize).any(|k| has(k) == false && (0..n as usize).all(|i| has(i) == false || adj[i][k]));
+            complete && joinable == false
+        }).collect();
+        let mut found: Vec<u32> = g.maximal_cliques()
+            .map(|c| c.iter().map(|v| 1 << *v.get()).sum())
+            .collect();
+        expected.sort();
+        found.sort();
+        assert_eq!(expected, found);
+        let biggest = expected.iter().map(|s| s.count_ones()).max().unwrap() as usize;
+        assert_eq!(biggest, g.maximum_clique().len());
+    }
+}
+
+fn is_proper(g: &UnweightedUndirectedGraph<u8>, colors: &HashMap<&u8, usize>) -> bool {
+    colors.len() == g.order() && g.vertices().all(|v| {
+        g.get_neighbors(v).all(|n| n.get() == v.get() || colors[v.get()] != colors[n.get()])
+    })
+}
+
+fn count_colors(colors: &HashMap<&u8, usize>) -> usize {
+    colors.values().collect::<HashSet<_>>().len()
+}
+
+#[test]
+fn coloring() {
+    // the Grötzsch graph: no triangles but still needs four colors
+    let mut grotzsch = UnweightedUndirectedGraph::<u8>::new();
+    for i in 0..11 { grotzsch.insert_vertex(i); }
+    for i in 0..5 {
+        let next = (i + 1) % 5;
+        grotzsch.insert_undirected_edge(UnweightedEdge, &i, &next);
+        grotzsch.insert_undirected_edge(UnweightedEdge, &(5 + i), &next);
+        grotzsch.insert_undirected_edge(UnweightedEdge, &i, &(5 + next));
+        grotzsch.insert_undirected_edge(UnweightedEdge, &(5 + i), &10);
+    }
+    assert_eq!(2, grotzsch.maximum_clique().len());
+    assert_eq!(4, grotzsch.chromatic_number());
+    assert_eq!(3, petersen().chromatic_number());
+
+    // a tree with a self-loop, which doesn't count
+    let mut tree = UnweightedUndirectedGraph::<u8>::new();
+    for i in 0..15 { tree.insert_vertex(i); }
+    for i in 1..15 { tree.insert_undirected_edge(UnweightedEdge, &i, &((i - 1) / 2)); }
+    tree.insert_undirected_edge(UnweightedEdge, &3, &3);
+    for g in &[&grotzsch, &petersen(), &tree] {
+        let exact = g.exact_coloring();
+        assert!(is_proper(g, &exact));
+        for colors in &[g.greedy_coloring(::ColorOrder::LargestFirst),
+                        g.greedy_coloring(::ColorOrder::SmallestLast),
+                        g.dsatur_coloring()] {
+            assert!(is_proper(g, colors));
+            assert!(count_colors(colors) >= count_colors(&exact));
+        }
+    }
+    assert_eq!(2, count_colors(&tree.greedy_coloring(::ColorOrder::SmallestLast)));
+    assert_eq!(2, count_colors(&tree.dsatur_coloring()));
+    assert_eq!(0, UnweightedUndirectedGraph::<u8>::new().chromatic_number());
+}
+
+#[test]
+fn coloring_random() {
+    let mut seed: u32 = 1852;
+    let mut rand = move |n: u32| {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        (seed >> 16) % n
+    };
+    for _ in 0..60 {
+        let n = 1 + rand(8) as u8;
+        let mut g = UnweightedUndirectedGraph::<u8>::new();
+        for i in 0..n { g.insert_vertex(i); }
+        let mut edges = vec![];
+        for l in 0..n {
+            for r in l + 1 .. n {
+                if rand(2) == 0 {
+                    g.insert_undirected_edge(UnweightedEdge, &l, &r);
+                    edges.push((l as usize, r as usize));
+                }
+            }
+        }
+        // fewest colors for which some assignment works
+        let fewest = (1..).find(|&k: &usize| {
+            (0 .. k.pow(n as u32)).any(|code| {
+                let color = |v: usize| code / k.pow(v as u32) % k;
+                edges.iter().all(|&(l, r)| color(l) != color(r))
+            })
+        }).unwrap();
+        let exact = g.exact_coloring();
+        assert!(is_proper(&g, &exact));
+        assert_eq!(fewest, count_colors(&exact));
+        assert_eq!(fewest, g.chromatic_number());
+        assert!(is_proper(&g, &g.dsatur_coloring()));
+    }
+}
+
+#[test]
+fn page_rank() {
+    let config = ::PageRankConfig::default();
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+    let mut g = DiGraph::<char, UnweightedEdge>::new();
+    for c in "ABCD".chars() { g.insert_vertex(c); }
+    for &(l, r) in &[('A', 'B'), ('A', 'C'), ('B', 'C'), ('C', 'A'), ('D', 'C')] {
+        g.insert_directed_edge(UnweightedEdge, &l, &r);
+    }
+    let ranks = g.page_rank(&config);
+    assert!(close(1.0, ranks.values().sum()));
+    for &(v, r) in &[('A', 0.372527), ('B', 0.195824), ('C', 0.394149), ('D', 0.0375)] {
+        assert!(close(r, ranks[&v]), "{}: {}", v, ranks[&v]);
+    }
+
+    // personalized ranks can't leak out of what the seeds reach
+    let a = g.get_vertex(&'A').unwrap();
+    let near_a = g.personalized_page_rank(&[a], &config);
+    assert!(close(1.0, near_a.values().sum()));
+    assert_eq!(0.0, near_a[&'D']);
+    assert!(near_a[&'A'] > ranks[&'A']);
+
+    // where a dead end's rank goes
+    let mut chain = DiGraph::<u8, UnweightedEdge>::new();
+    for i in 0..3 { chain.insert_vertex(i); }
+    chain.insert_directed_edge(UnweightedEdge, &0, &1);
+    chain.insert_directed_edge(UnweightedEdge, &1, &2);
+    let start = chain.get_vertex(&0).unwrap();
+    for &dangling in &[::Dangling::Teleport, ::Dangling::Uniform, ::Dangling::Stay] {
+        let config = ::PageRankConfig { dangling, ..config };
+        let ranks = chain.page_rank(&config);
+        assert!(close(1.0, ranks.values().sum()));
+        assert!(ranks[&0] < ranks[&1] && ranks[&1] < ranks[&2]);
+        let near = chain.personalized_page_rank(&[start], &config);
+        assert!(close(1.0, near.values().sum()));
+        match dangling {
+            ::Dangling::Teleport => assert!(near[&0] > near[&1]),
+            // whatever stays at the end only gets out by teleporting
+            ::Dangling::Stay => assert!(near[&2] > near[&0]),
+            ::Dangling::Uniform => assert!(close(ranks[&2], near[&2]) == false),
+        }
+    }
+    assert!(DiGraph::<u8, UnweightedEdge>::new().page_rank(&config).is_empty());
+}
+
+#[test]
+fn weighted_page_rank() {
+    let config = ::PageRankConfig::default();
+    let mut g = DiGraph::<u8, u32>::new();
+    for i in 0..3 { g.insert_vertex(i); }
+    for &(l, r, w) in &[(0, 1, 3), (0, 2, 1), (1, 0, 1), (2, 0, 1)] {
+        g.insert_directed_edge(w, &l, &r);
+    }
+    let plain = g.page_rank(&config);
+    let weighted = g.weighted_page_rank(&config);
+    assert!((plain[&1] - plain[&2]).abs() < 1e-9);
+    assert!((weighted.values().sum::<f64>() - 1.0).abs() < 1e-6);
+    // 1 gets three times the rank 2 does from 0, plus the same teleports
+    let teleported = 0.15 / 3.0;
+    assert!(((weighted[&1] - teleported) - 3.0 * (weighted[&2] - teleported)).abs() < 1e-6);
+
+    let two = g.get_vertex(&2).unwrap();
+    let near_two = g.weighted_personalized_page_rank(&[two], &config);
+    assert!(near_two[&2] > weighted[&2] && near_two[&1] < weighted[&1]);
+}
+
+#[test]
+fn betweenness_centrality() {
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+    let path = weighted(&[(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 4, 1)]);
+    let through = path.betweenness_centrality();
+    for &(v, b) in &[(0, 0.0), (1, 3.0), (2, 4.0), (3, 3.0), (4, 0.0)] {
+        assert!(close(b, through[&v]));
+    }
+    for ((l, _, r), b) in path.edge_betweenness_centrality() {
+        let outer = [*l.get(), *r.get()].iter().any(|&v| v == 0 || v == 4);
+        assert!(close(if outer { 4.0 } else { 6.0 }, b));
+    }
+
+    // opposite corners of a square split their two paths evenly
+    let square = weighted(&[(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 0, 1)]);
+    assert!(square.betweenness_centrality().values().all(|&b| close(0.5, b)));
+    assert!(square.edge_betweenness_centrality().iter().all(|&(_, b)| close(2.0, b)));
+
+    // the long way around is shorter by weight
+    let triangle = weighted(&[(0, 1, 1), (1, 2, 1), (0, 2, 5)]);
+    assert!(close(0.0, triangle.betweenness_centrality()[&1]));
+    assert!(close(1.0, triangle.weighted_betweenness_centrality()[&1]));
+    for ((l, _, r), b) in triangle.weighted_edge_betweenness_centrality() {
+        let long = *l.get() + *r.get() == 2;
+        assert!(close(if long { 0.0 } else { 2.0 }, b));
+    }
+
+    let mut chain = DiGraph::<u8, UnweightedEdge>::new();
+    for i in 0..3 { chain.insert_vertex(i); }
+    chain.insert_directed_edge(UnweightedEdge, &0, &1);
+    chain.insert_directed_edge(UnweightedEdge, &1, &2);
+    let through = chain.betweenness_centrality();
+    assert!(close(1.0, through[&1]) && close(0.0, through[&0]));
+
+    // breadth-first search and Dijkstra agree when every weight is one
+    let mut seed: u32 = 7;
+    let mut rand = move |n: u32| {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        (seed >> 16) % n
+    };
+    for _ in 0..20 {
+        let edges: Vec<(u8, u8, i32)> = (0..14).map(|_| (rand(9) as u8, rand(9) as u8, 1)).collect();
+        let g = weighted(&edges);
+        let (plain, weighted) = (g.betweenness_centrality(), g.weighted_betweenness_centrality());
+        assert!(plain.iter().all(|(v, &b)| close(b, weighted[v])), "{:?}", edges);
+        let (plain, weighted) = (g.closeness_centrality(), g.weighted_closeness_centrality());
+        assert!(plain.iter().all(|(v, &c)| close(c, weighted[v])), "{:?}", edges);
+    }
+}
+
+#[test]
+fn distance_centrality() {
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+    let path = weighted(&[(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 4, 1)]);
+    let closeness = path.closeness_centrality();
+    assert!(close(0.4, closeness[&0]) && close(4.0 / 6.0, closeness[&2]));
+    let harmonic = path.harmonic_centrality();
+    assert!(close(1.0 + 1.0 / 2.0 + 1.0 / 3.0 + 1.0 / 4.0, harmonic[&0]));
+    assert!(close(3.0, harmonic[&2]));
+
+    // the end of a chain reaches nothing, and the start only reaches half
+    let mut chain = DiGraph::<u8, u32>::new();
+    for i in 0..3 { chain.insert_vertex(i); }
+    chain.insert_directed_edge(2, &0, &1);
+    chain.insert_directed_edge(4, &1, &2);
+    let closeness = chain.closeness_centrality();
+    assert!(close(0.0, closeness[&2]) && close(0.5, closeness[&1]));
+    assert!(close(2.0 / 8.0, chain.weighted_closeness_centrality()[&0]));
+    assert!(close(1.0 / 2.0 + 1.0 / 6.0, chain.weighted_harmonic_centrality()[&0]));
+}
+
+#[test]
+fn spectral_centrality() {
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+    // a star's center scores the square root of its degree times a leaf's
+    let star = weighted(&[(0, 1, 1), (0, 2, 1), (0, 3, 1), (0, 4, 1)]);
+    let x = star.eigenvector_centrality();
+    assert!(close(1.0, x.values().map(|a| a * a).sum()));
+    assert!(close(2.0, x[&0] / x[&1]));
+    // doubling every weight doesn't change anything
+    let heavy = weighted(&[(0, 1, 2), (0, 2, 2), (0, 3, 2), (0, 4, 2)]);
+    assert!(close(x[&0], heavy.weighted_eigenvector_centrality()[&0]));
+
+    let mut chain = DiGraph::<u8, u32>::new();
+    for i in 0..3 { chain.insert_vertex(i); }
+    chain.insert_directed_edge(1, &0, &1);
+    chain.insert_directed_edge(2, &1, &2);
+    let katz = chain.katz_centrality(0.5).unwrap();
+    assert!(close(1.0, katz[&0]) && close(1.5, katz[&1]) && close(1.75, katz[&2]));
+    let katz = chain.weighted_katz_centrality(0.5).unwrap();
+    assert!(close(1.5, katz[&1]) && close(2.5, katz[&2]));
+    // a cycle's largest eigenvalue is one
+    chain.insert_directed_edge(1, &2, &0);
+    assert!(chain.katz_centrality(0.9).is_some());
+    assert!(chain.katz_centrality(1.1).is_none());
+
+    // 0 and 1 both point to 2 and 3, but only 0 points to 4
+    let mut web = DiGraph::<u8, u32>::new();
+    for i in 0..5 { web.insert_vertex(i); }
+    for &(l, r) in &[(0, 2), (0, 3), (1, 2), (1, 3), (0, 4)] {
+        web.insert_directed_edge(if r == 4 { 10 } else { 1 }, &l, &r);
+    }
+    let (hubs, auths) = web.hits();
+    assert!(close(1.0, hubs.values().sum()) && close(1.0, auths.values().sum()));
+    assert!(hubs[&0] > hubs[&1] && hubs[&1] > hubs[&2]);
+    assert!(auths[&2] > auths[&4] && close(auths[&2], auths[&3]) && close(0.0, auths[&0]));
+    let (hubs, auths) = web.weighted_hits();
+    assert!(auths[&4] > auths[&2] && hubs[&0] > hubs[&1]);
+}
+
+fn karate() -> UnweightedUndirectedGraph<u8> {
+    // Zachary's karate club, which split in two between members 1 and 34
+    let edges = [(2, 1), (3, 1), (3, 2), (4, 1), (4, 2), (4, 3), (5, 1), (6, 1), (7, 1), (7, 5),
+        (7, 6), (8, 1), (8, 2), (8, 3), (8, 4), (9, 1), (9, 3), (10, 3), (11, 1), (11, 5), (11, 6),
+        (12, 1), (13, 1), (13, 4), (14, 1), (14, 2), (14, 3), (14, 4), (17, 6), (17, 7), (18, 1),
+        (18, 2), (20, 1), (20, 2), (22, 1), (22, 2), (26, 24), (26, 25), (28, 3), (28, 24),
+        (28, 25), (29, 3), (30, 24), (30, 27), (31, 2), (31, 9), (32, 1), (32, 25), (32, 26),
+        (32, 29), (33, 3), (33, 9), (33, 15), (33, 16), (33, 19), (33, 21), (33, 23), (33, 24),
+        (33, 30), (33, 31), (33, 32), (34, 9), (34, 10), (34, 14), (34, 15), (34, 16), (34, 19),
+        (34, 20), (34, 21), (34, 23), (34, 24), (34, 27), (34, 28), (34, 29), (34, 30), (34, 31),
+        (34, 32), (34, 33)];
+    let mut g = UnweightedUndirectedGraph::new();
+    for i in 1..35 { g.insert_vertex(i); }
+    for &(l, r) in edges.iter() { g.insert_undirected_edge(UnweightedEdge, &l, &r); }
+    g
+}
+
+// each community's vertices in order, and the communities in order
+fn partition<E: ::WeightT>(found: &::Communities<u8, E>) -> Vec<Vec<u8>> {
+    let mut communities: Vec<Vec<u8>> = found.communities().iter()
+        .map(|c| {
+            let mut c: Vec<u8> = c.iter().map(|v| *v.get()).collect();
+            c.sort();
+            c
+        })
+        .collect();
+    communities.sort();
+    communities
+}
+
+#[test]
+fn louvain() {
+    // two K5s joined by a single edge
+    let mut edges = vec![(0, 5, 1)];
+    for i in 0..5 {
+        for j in i + 1 .. 5 {
+            edges.push((i, j, 1));
+            edges.push((5 + i, 5 + j, 1));
+        }
+    }
+    let g = weighted(&edges);
+    let found = g.louvain();
+    assert_eq!(2, found.communities().len());
+    for c in found.communities() {
+        let side: HashSet<bool> = c.iter().map(|v| *v.get() < 5).collect();
+        assert_eq!((5, 1), (c.len(), side.len()));
+    }
+    assert!((20.0 / 21.0 - 0.5 - found.modularity()).abs() < 1e-9);
+    assert_eq!(found.community_of(&0), found.community_of(&4));
+    assert!(found.community_of(&0) != found.community_of(&5));
+    assert_eq!(None, found.community_of(&10));
+
+    // weights pull the middle pair apart
+    let g = weighted(&[(0, 1, 10), (1, 2, 1), (2, 3, 10)]);
+    let found = g.louvain();
+    assert_eq!(found.community_of(&0), found.community_of(&1));
+    assert_eq!(found.community_of(&2), found.community_of(&3));
+    assert!(found.community_of(&1) != found.community_of(&2));
+
+    // the best known split of the karate club, scoring 0.4188
+    let karate = karate();
+    let found = karate.louvain();
+    assert_eq!(vec![vec![1, 2, 3, 4, 8, 10, 12, 13, 14, 18, 20, 22],
+                    vec![5, 6, 7, 11, 17],
+                    vec![9, 15, 16, 19, 21, 23, 27, 30, 31, 33, 34],
+                    vec![24, 25, 26, 28, 29, 32]],
+               partition(&found));
+    assert!((0.4188034188 - found.modularity()).abs() < 1e-9);
+
+    // nothing to cluster
+    let mut lonely = UnweightedUndirectedGraph::<u8>::new();
+    for i in 0..3 { lonely.insert_vertex(i); }
+    let found = lonely.louvain();
+    assert_eq!((3, 0.0), (found.communities().len(), found.modularity()));
+}
+
+#[test]
+fn label_propagation() {
+    let mut edges = vec![(0, 5, 1)];
+    for i in 0..5 {
+        for j in i + 1 .. 5 {
+            edges.push((i, j, 1));
+            edges.push((5 + i, 5 + j, 1));
+        }
+    }
+    let g = weighted(&edges);
+    for seed in 0..20 {
+        let found = g.label_propagation(seed);
+        assert_eq!(vec![vec![0, 1, 2, 3, 4], vec![5, 6, 7, 8, 9]], partition(&found));
+        assert!((20.0 / 21.0 - 0.5 - found.modularity()).abs() < 1e-9);
+    }
+
+    // close to Louvain's split, and the same every time for the same seed
+    let karate = karate();
+    let found = karate.label_propagation(1);
+    assert_eq!(vec![vec![1, 2, 3, 4, 8, 10, 12, 13, 14, 18, 20, 22, 29],
+                    vec![5, 6, 7, 11, 17],
+                    vec![9, 15, 16, 19, 21, 23, 24, 27, 28, 30, 31, 33, 34],
+                    vec![25, 26, 32]],
+               partition(&found));
+    assert_eq!(partition(&found), partition(&karate.label_propagation(1)));
+}
+
+#[test]
+fn triangles() {
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+    // a triangle with a tail, a doubled edge and a self-loop
+    let paw = weighted(&[(0, 1, 1), (1, 2, 1), (2, 0, 1), (2, 3, 1), (0, 1, 2), (3, 3, 1)]);
+    let through = paw.triangles();
+    assert_eq!(vec![1, 1, 1, 0], (0..4).map(|v| through[&v]).collect::<Vec<_>>());
+    assert_eq!(1, paw.triangle_count());
+    let local = paw.clustering_coefficient();
+    for &(v, c) in &[(0, 1.0), (1, 1.0), (2, 1.0 / 3.0), (3, 0.0)] {
+        assert!(close(c, local[&v]));
+    }
+    assert!(close(7.0 / 12.0, paw.average_clustering()));
+    assert!(close(3.0 / 5.0, paw.transitivity()));
+
+    let g = petersen();
+    assert_eq!(0, g.triangle_count());
+    assert!(close(0.0, g.transitivity()) && close(0.0, g.average_clustering()));
+
+    let karate = karate();
+    assert_eq!(45, karate.triangle_count());
+    assert!((karate.transitivity() - 0.2557).abs() < 1e-4);
+    assert!((karate.average_clustering() - 0.5706).abs() < 1e-4);
+    assert_eq!(18, karate.triangles()[&1]);
+
+    assert_eq!(0, UnweightedUndirectedGraph::<u8>::new().triangle_count());
+    assert!(close(0.0, UnweightedUndirectedGraph::<u8>::new().average_clustering()));
+}
+
+#[test]
+fn triangles_random() {
+    let mut seed: u32 = 1736;
+    let mut rand = move |n: u32| {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        (seed >> 16) % n
+    };
+    for _ in 0..50 {
+        let n = 1 + rand(12) as u8;
+        let mut g = UnweightedUndirectedGraph::<u8>::new();
+        for i in 0..n { g.insert_vertex(i); }
+        let mut adj = vec![vec![false; n as usize]; n as usize];
+        for l in 0..n {
+            for r in l + 1 .. n {
+                if rand(2) == 0 {
+                    g.insert_undirected_edge(UnweightedEdge, &l, &r);
+                    adj[l as usize][r as usize] = true;
+                    adj[r as usize][l as usize] = true;
+                }
+            }
+        }
+        let n = n as usize;
+        let mut through = vec![0; n];
+        for i in 0..n {
+            for j in i + 1 .. n {
+                for k in j + 1 .. n {
+                    if adj[i][j] && adj[j][k] && adj[i][k] {
+                        for &v in &[i, j, k] { through[v] += 1; }
+                    }
+                }
+            }
+        }
+        let found = g.triangles();
+        assert!((0..n).all(|v| found[&(v as u8)] == through[v]));
+        assert_eq!(through.iter().sum::<usize>() / 3, g.triangle_count());
+    }
+}
+
 /*
 #[test]
 fn foo() {
@@ -200,11 +1988,14 @@ fn directed_neighborhood() {
     assert_eq!(0, g.get_parents(a).count());
     assert_eq!(1, g.get_children(a).count());
     assert_eq!(1, a.degree());
+    assert_eq!((0, 1), (a.in_degree(), a.out_degree()));
 
     let b = g.get_vertex(&'B').unwrap();
     assert_eq!(1, g.get_parents(b).count());
     assert_eq!(0, g.get_children(b).count());
-    assert_eq!(1, b.degree()); // TODO should this be 0 or 1?
+    // `degree` counts edges in either direction
+    assert_eq!(1, b.degree());
+    assert_eq!((1, 0), (b.in_degree(), b.out_degree()));
 
     assert_eq!(Some(&11), a.edge_to(&'B'));
     assert_eq!(None, b.edge_to(&'A'));
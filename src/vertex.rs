@@ -1,7 +1,9 @@
 
 use std::fmt;
 use std::rc::Rc;
-use std::hash::Hash;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::borrow::Borrow;
 use std::marker::PhantomData;
 
@@ -15,16 +17,27 @@ use edge::{EdgeT, GenEdge, DirEdge, UndirEdge};
 pub trait NodeT: fmt::Debug + Eq + Hash {}
 impl<T: fmt::Debug + Eq + Hash> NodeT for T {}
 
+/// Hash of a vertex's value, for finding the edges to it without cloning
+///  the `Rc` (which would throw off `is_shared`)
+fn key<V: NodeT>(v: &V) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    v.hash(&mut hasher);
+    hasher.finish()
+}
+
 #[derive(Debug)]
 pub struct Vertex<V: NodeT, E: EdgeT, D: DirT<V,E>> {
     val: Rc<V>,
     hood: D,
+    // positions in `hood.get_reachable()` of the edges to each vertex, keyed
+    //  by `key` of its value, so `edge_to` doesn't have to scan every edge
+    reach: HashMap<u64, Vec<usize>>,
     _e: PhantomData<E>,
 }
 
 impl<V: NodeT, E: EdgeT, D: DirT<V,E>> Vertex<V,E,D> {
     pub(crate) fn new(val: V) -> Self {
-        Vertex { val: Rc::new(val), hood: D::new(), _e: PhantomData, }
+        Vertex { val: Rc::new(val), hood: D::new(), reach: HashMap::new(), _e: PhantomData, }
     }
     pub(super) fn get_ref(&self) -> Rc<V> {
         self.val.clone()
@@ -33,15 +46,21 @@ impl<V: NodeT, E: EdgeT, D: DirT<V,E>> Vertex<V,E,D> {
     pub fn get(&self) -> &V {
         &self.val
     }
+    /// Number of edges touching this vertex, with self-loops counted twice;
+    ///  for a directed vertex that's `in_degree() + out_degree()`
     pub fn degree(&self) -> usize {
         self.hood.degree()
     }
+    /// The first edge from here to `other`, found by hash rather than by
+    ///  scanning every edge
     pub fn edge_to(&self, other: &V) -> Option<&E> {
         // TODO maybe this should return `Rc<E>`?
         // It's a little clunkier but it means the vertex is still mutable
         // but if `self` exists at all then the graph is immutable, 
         //  and `self` isn't mutated anyway
-        self.hood.get_reachable().iter()
+        let reachable = self.hood.get_reachable();
+        self.reach.get(&key(other))?.iter()
+            .map(|&i| &reachable[i])
             .find(|&e| e.get_end() == other)
             .map(|e| e.as_ref())
     }
@@ -50,14 +69,46 @@ impl<V: NodeT, E: EdgeT, D: DirT<V,E>> Vertex<V,E,D> {
     }
 
     pub(super) fn register_as_src(&mut self, edge: GenEdge<V,E,D>) {
+        let before = self.hood.get_reachable().len();
         self.hood.push_dst(edge);
+        self.index_from(before);
     }
     pub(super) fn register_as_dst(&mut self, edge: GenEdge<V,E,D>) {
+        let before = self.hood.get_reachable().len();
         self.hood.push_src(edge);
+        self.index_from(before);
+    }
+    /// Index the reachable edges from position `start` on
+    fn index_from(&mut self, start: usize) {
+        for (i, e) in self.hood.get_reachable().iter().enumerate().skip(start) {
+            self.reach.entry(key(e.get_end())).or_default().push(i);
+        }
     }
     pub(super) fn get_reachable(&self) -> &[GenEdge<V,E,D>] {
         self.hood.get_reachable()
     }
+    pub(super) fn get_backward(&self) -> &[GenEdge<V,E,D>] {
+        self.hood.get_backward()
+    }
+    pub(super) fn unregister(&mut self, edge: &E) -> Option<GenEdge<V,E,D>> {
+        let removed = self.hood.remove(edge);
+        // everything after the removed edge has moved down one
+        self.reach.clear();
+        self.index_from(0);
+        removed
+    }
+    pub(super) fn unregister_all(&mut self) -> Vec<GenEdge<V,E,D>> {
+        self.reach.clear();
+        self.hood.drain()
+    }
+    /// Whether anything besides the graph and its edges holds a ref to `val`
+    pub(super) fn is_shared(&self) -> bool {
+        // one for `val`, one for the `nodes` key, one per edge ending here
+        Rc::strong_count(&self.val) > 2 + self.degree()
+    }
+    pub(super) fn into_inner(self) -> Option<V> {
+        Rc::try_unwrap(self.val).ok()
+    }
 }
 
 impl<V: NodeT, E: EdgeT, D: DirT<V,E>> Borrow<V> for Vertex<V,E,D> {
@@ -79,6 +130,14 @@ impl<V: NodeT, E: EdgeT> Vertex<V, E, Undir<V,E>> {
 }
 
 impl<V: NodeT, E: EdgeT> Vertex<V, E, Dir<V,E>> {
+    /// Number of edges ending here
+    pub fn in_degree(&self) -> usize {
+        self.get_parent_edges().len()
+    }
+    /// Number of edges starting here
+    pub fn out_degree(&self) -> usize {
+        self.get_child_edges().len()
+    }
     pub(super) fn get_parent_edges(&self) -> &[DirEdge<V,E>] {
         self.hood.get_parents()
     }